@@ -32,12 +32,15 @@ DEALINGS IN THE SOFTWARE.
 #![deny(clippy::all)]
 #![feature(box_patterns)]
 
-use std::{cell::RefCell, path::PathBuf, rc::Rc, sync::Arc};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc, sync::Arc};
 
 use auto_cjs::contains_cjs;
 use either::Either;
 use fxhash::FxHashSet;
-use next_transform_dynamic::{next_dynamic, NextDynamicMode};
+use next_transform_dynamic::{
+    dead_branches::eliminate_dead_branches, edge_runtime::edge_runtime_check, next_dynamic,
+    NextDynamicMode,
+};
 use next_transform_font::next_font_loaders;
 use serde::Deserialize;
 use turbopack_binding::swc::{
@@ -147,6 +150,16 @@ pub struct TransformOptions {
 
     #[serde(default)]
     pub optimize_server_react: Option<optimize_server_react::Config>,
+
+    /// The `process.env` variables and `typeof` checks (e.g. `typeof window`)
+    /// known at compile time, to remove the branches they rule out before
+    /// `next_dynamic`.
+    #[serde(default)]
+    pub dead_branches: Option<HashMap<String, String>>,
+
+    /// Reports the Node.js APIs used by modules running in the edge runtime.
+    #[serde(default)]
+    pub is_edge_runtime: bool,
 }
 
 pub fn custom_before_pass<'a, C: Comments + 'a>(
@@ -223,6 +236,16 @@ where
             !opts.disable_next_ssg
         ),
         amp_attributes::amp_attributes(),
+        match &opts.dead_branches {
+            Some(env) => Either::Left(eliminate_dead_branches(env.clone())),
+            None => Either::Right(noop()),
+        },
+        // Must run after the dead branches are removed, see `edge_runtime_check`.
+        Optional::new(
+            edge_runtime_check(file.name.clone(), opts.pages_dir.clone(), unresolved_mark),
+            opts.is_edge_runtime
+        ),
+        // Must run after styled-jsx, see `next_dynamic`.
         next_dynamic(
             opts.is_development,
             opts.is_server_compiler,
@@ -237,7 +260,8 @@ where
             },
             NextDynamicMode::Webpack,
             file.name.clone(),
-            opts.pages_dir.clone()
+            opts.pages_dir.clone(),
+            Default::default()
         ),
         Optional::new(
             page_config::page_config(opts.is_development, opts.is_page_file),
@@ -59,6 +59,7 @@ fn next_dynamic_errors(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
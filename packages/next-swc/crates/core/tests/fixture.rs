@@ -68,6 +68,7 @@ fn next_dynamic_fixture(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
@@ -84,6 +85,7 @@ fn next_dynamic_fixture(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
@@ -100,6 +102,7 @@ fn next_dynamic_fixture(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
@@ -123,6 +126,7 @@ fn app_dir_next_dynamic_fixture(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
@@ -139,6 +143,7 @@ fn app_dir_next_dynamic_fixture(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
@@ -155,6 +160,7 @@ fn app_dir_next_dynamic_fixture(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         &input,
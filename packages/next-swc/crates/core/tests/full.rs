@@ -81,6 +81,8 @@ fn test(input: &Path, minify: bool) {
                 auto_modularize_imports: None,
                 optimize_barrel_exports: None,
                 optimize_server_react: None,
+                dead_branches: None,
+                is_edge_runtime: false,
             };
 
             let unresolved_mark = Mark::new();
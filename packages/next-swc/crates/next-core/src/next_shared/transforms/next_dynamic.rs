@@ -68,6 +68,7 @@ impl CustomTransformer for NextJsDynamic {
             NextDynamicMode::Webpack,
             FileName::Real(ctx.file_path_str.into()),
             self.pages_dir.clone(),
+            Default::default(),
         ));
 
         Ok(())
@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use next_transform_dynamic::{next_dynamic, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::{
+        ast::{EsVersion, Module},
+        parser::{parse_file_as_module, EsConfig, Syntax},
+        visit::FoldWith,
+    },
+};
+
+/// A module with many statements and a few `dynamic()` calls, so that the
+/// cost of injecting the Turbopack imports before the existing items shows.
+fn large_module() -> Module {
+    let mut src = "import dynamic from 'next/dynamic'\n".to_string();
+    for i in 0..5_000 {
+        src.push_str(&format!("export const value{i} = {i}\n"));
+    }
+    for i in 0..10 {
+        src.push_str(&format!(
+            "export const Component{i} = dynamic(() => import('../components/component{i}'))\n"
+        ));
+    }
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, src);
+    parse_file_as_module(
+        &fm,
+        Syntax::Es(EsConfig::default()),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    )
+    .unwrap()
+}
+
+fn bench_turbopack_imports(c: &mut Criterion) {
+    let module = large_module();
+
+    c.bench_function("next_dynamic/turbopack_imports", |b| {
+        GLOBALS.set(&Default::default(), || {
+            b.iter_batched(
+                || module.clone(),
+                |module| {
+                    black_box(module.fold_with(&mut next_dynamic(
+                        true,
+                        false,
+                        false,
+                        NextDynamicMode::Turbopack {
+                            dynamic_transition_names: "next-client-chunks".into(),
+                            key_export: Default::default(),
+                        },
+                        FileName::Real("/some-project/src/some-file.js".into()),
+                        Some("/some-project/src".into()),
+                        Default::default(),
+                    )))
+                },
+                BatchSize::LargeInput,
+            )
+        })
+    });
+}
+
+criterion_group!(benches, bench_turbopack_imports);
+criterion_main!(benches);
@@ -0,0 +1,146 @@
+//! Tracks the `import()` expressions which aren't wrapped in a `dynamic()`
+//! call, so that the router can preload their chunks.
+
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use swc_core::{
+    common::{
+        comments::{Comment, CommentKind, Comments},
+        DUMMY_SP,
+    },
+    ecma::{
+        ast::{CallExpr, Callee, Expr, Id, ImportDecl, ImportSpecifier, Lit, Str, Tpl},
+        visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith},
+    },
+};
+
+use crate::{paths, Config};
+
+/// A plain `import()` expression with a static specifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BareImport {
+    pub specifier: String,
+}
+
+/// Creates a SWC visitor collecting the `import()` expressions of a module
+/// which aren't wrapped in a `next/dynamic` call into `imports`.
+///
+/// A `webpackPrefetch` magic comment is also added to `comments` for the
+/// imports opting in to prefetching, whose specifier matches one of the
+/// `prefetch_specifiers`, or `*` patterns like `@/components/*`.
+///
+/// `dynamic` is imported from the `Config::dynamic_specifiers`, the other
+/// options of `config` being unused.
+pub fn bare_imports<C>(
+    imports: Arc<Mutex<Vec<BareImport>>>,
+    config: Config,
+    prefetch_specifiers: Vec<String>,
+    comments: Option<C>,
+) -> impl Fold
+where
+    C: Comments,
+{
+    as_folder(BareImportTracker {
+        imports,
+        config,
+        prefetch_specifiers,
+        comments,
+        dynamic_bindings: vec![],
+        dynamic_call_depth: 0,
+    })
+}
+
+struct BareImportTracker<C>
+where
+    C: Comments,
+{
+    imports: Arc<Mutex<Vec<BareImport>>>,
+    config: Config,
+    prefetch_specifiers: Vec<String>,
+    comments: Option<C>,
+    dynamic_bindings: Vec<Id>,
+    /// The number of `dynamic()` calls the visitor is currently in.
+    dynamic_call_depth: usize,
+}
+
+impl<C> VisitMut for BareImportTracker<C>
+where
+    C: Comments,
+{
+    fn visit_mut_import_decl(&mut self, decl: &mut ImportDecl) {
+        if !decl.type_only && self.config.is_dynamic_specifier(&decl.src.value) {
+            for specifier in &decl.specifiers {
+                if let ImportSpecifier::Default(default_specifier) = specifier {
+                    self.dynamic_bindings.push(default_specifier.local.to_id());
+                }
+            }
+        }
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        if self.is_dynamic_call(call) {
+            self.dynamic_call_depth += 1;
+            call.visit_mut_children_with(self);
+            self.dynamic_call_depth -= 1;
+            return;
+        }
+
+        if let Callee::Import(..) = call.callee {
+            if self.dynamic_call_depth == 0 {
+                self.track(call);
+            }
+        }
+
+        call.visit_mut_children_with(self);
+    }
+
+    noop_visit_mut_type!();
+}
+
+impl<C> BareImportTracker<C>
+where
+    C: Comments,
+{
+    fn is_dynamic_call(&self, call: &CallExpr) -> bool {
+        match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(ident) => self.dynamic_bindings.contains(&ident.to_id()),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Records an `import()` call, if its specifier is static.
+    fn track(&mut self, call: &CallExpr) {
+        let Some(arg) = call.args.first() else {
+            return;
+        };
+        let (specifier, span) = match &*arg.expr {
+            Expr::Lit(Lit::Str(Str { value, span, .. })) => (value.to_string(), *span),
+            Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => {
+                (quasis[0].raw.to_string(), quasis[0].span)
+            }
+            _ => return,
+        };
+
+        let prefetch = self
+            .prefetch_specifiers
+            .iter()
+            .any(|pattern| paths::matches_pattern(pattern, &specifier));
+        self.imports.lock().unwrap().push(BareImport { specifier });
+
+        if let Some(comments) = self.comments.as_ref().filter(|_| prefetch) {
+            comments.add_leading(
+                span.lo,
+                Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: " webpackPrefetch: true ".into(),
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Transforms the modules of a whole compilation at once, e.g. for `next build
+//! --no-cache`, sharing the config and the buffers of the transform between
+//! modules. With the `rayon` feature, the modules are transformed in parallel.
+
+use std::{path::Path, sync::Arc};
+
+use swc_core::{
+    common::{errors::HANDLER, FileName},
+    ecma::{ast::Program, visit::FoldWith},
+};
+
+use crate::{
+    next_dynamic_patcher_shared, Config, DynamicDiagnostic, InjectedItems, NextDynamicMode,
+    TransformStats,
+};
+
+/// The transformed module, along with the metadata of its transform.
+#[derive(Debug, Clone)]
+pub struct TransformResult {
+    pub filename: FileName,
+    pub program: Program,
+    /// The diagnostics of the module, unless they're emitted to the `HANDLER`
+    /// of the caller, i.e. when it's set and `Config::collect_diagnostics`
+    /// isn't.
+    pub diagnostics: Vec<DynamicDiagnostic>,
+    pub stats: TransformStats,
+    pub injected_items: InjectedItems,
+}
+
+/// Transforms `modules` with the same mode and config, returning their results
+/// in the same order.
+///
+/// The modules are transformed one after the other by the same
+/// [`NextDynamicPatcher`](crate::NextDynamicPatcher), or, with the `rayon`
+/// feature, split into a chunk per thread of the rayon pool, each transformed
+/// by its own patcher. The caller must set the swc `GLOBALS` on the current
+/// thread. The diagnostics of each module are collected while the modules are
+/// transformed and, unless `Config::collect_diagnostics` is set, emitted to the
+/// `HANDLER` of the current thread once they all are, in the order of the
+/// modules. The `HANDLER` of the threads transforming the modules is never
+/// used, even when the caller is itself a thread of the rayon pool.
+pub fn transform_batch(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    pages_dir: Option<Arc<Path>>,
+    modules: Vec<(FileName, Program)>,
+    config: Arc<Config>,
+) -> Vec<TransformResult> {
+    // The diagnostics are emitted after the join, as the chunks may run on
+    // threads with or without a `HANDLER`, including the current one.
+    let emits_diagnostics = !config.collect_diagnostics && HANDLER.is_set();
+    let mut results = map_chunks(modules, |modules| {
+        transform_chunk(
+            is_development,
+            is_server_compiler,
+            is_react_server_layer,
+            mode.clone(),
+            pages_dir.clone(),
+            modules,
+            config.clone(),
+        )
+    });
+
+    if emits_diagnostics {
+        for result in &mut results {
+            for diagnostic in result.diagnostics.drain(..) {
+                diagnostic.emit();
+            }
+        }
+    }
+    results
+}
+
+/// Splits `items` into a chunk per thread of the rayon pool, and maps the
+/// chunks in parallel with the swc `GLOBALS` of the current thread.
+#[cfg(feature = "rayon")]
+fn map_chunks<T, R>(items: Vec<T>, map: impl Fn(Vec<T>) -> Vec<R> + Sync) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+{
+    use rayon::prelude::*;
+    use swc_core::common::GLOBALS;
+
+    let chunk_size = items.len().div_ceil(rayon::current_num_threads()).max(1);
+    let mut items = items.into_iter();
+    let chunks = std::iter::from_fn(|| {
+        let chunk = items.by_ref().take(chunk_size).collect::<Vec<_>>();
+        (!chunk.is_empty()).then_some(chunk)
+    })
+    .collect::<Vec<_>>();
+
+    GLOBALS
+        .with(|globals| {
+            chunks
+                .into_par_iter()
+                .map(|chunk| GLOBALS.set(globals, || map(chunk)))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn map_chunks<T, R>(items: Vec<T>, map: impl Fn(Vec<T>) -> Vec<R>) -> Vec<R> {
+    map(items)
+}
+
+/// Transforms `modules` one after the other with the same patcher.
+fn transform_chunk(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    pages_dir: Option<Arc<Path>>,
+    modules: Vec<(FileName, Program)>,
+    config: Arc<Config>,
+) -> Vec<TransformResult> {
+    let mut modules = modules.into_iter().peekable();
+    let Some((filename, _)) = modules.peek() else {
+        return vec![];
+    };
+
+    let mut patcher = next_dynamic_patcher_shared(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        mode,
+        filename.clone(),
+        pages_dir,
+        config,
+    );
+    patcher.defers_diagnostics = true;
+    modules
+        .map(|(filename, program)| {
+            patcher.reset(filename.clone());
+            let program = program.fold_with(&mut patcher);
+            TransformResult {
+                filename,
+                program,
+                diagnostics: std::mem::take(&mut patcher.diagnostics),
+                stats: patcher.stats(),
+                injected_items: patcher.injected_items().clone(),
+            }
+        })
+        .collect()
+}
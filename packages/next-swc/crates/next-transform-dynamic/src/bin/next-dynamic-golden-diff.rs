@@ -0,0 +1,66 @@
+//! Prints the options generated for the `dynamic()` calls of modules when they
+//! differ across modes, e.g. `next-dynamic-golden-diff --dev src/page.js`.
+//! `--specifier=<specifier>` replaces the specifiers importing `next/dynamic`,
+//! e.g. for a re-export of it. Exits with status 1 when any call differs.
+
+use std::{env, fs, process};
+
+use next_transform_dynamic::golden_diff::golden_diff;
+use swc_core::common::FileName;
+
+fn main() {
+    let mut is_development = false;
+    let mut is_server_compiler = false;
+    let mut dynamic_specifiers: Option<Vec<String>> = None;
+    let mut files = vec![];
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--dev" => is_development = true,
+            "--server" => is_server_compiler = true,
+            _ => match arg.strip_prefix("--specifier=") {
+                Some(specifier) => dynamic_specifiers
+                    .get_or_insert_with(Vec::new)
+                    .push(specifier.into()),
+                None => files.push(arg),
+            },
+        }
+    }
+
+    if files.is_empty() {
+        eprintln!(
+            "usage: next-dynamic-golden-diff [--dev] [--server] [--specifier=<specifier>]... \
+             <file>..."
+        );
+        process::exit(2);
+    }
+
+    let mut has_diff = false;
+    for file in files {
+        let source = fs::read_to_string(&file).unwrap_or_else(|err| {
+            eprintln!("{file}: {err}");
+            process::exit(2);
+        });
+        let calls = golden_diff(
+            &source,
+            FileName::Real(file.clone().into()),
+            is_development,
+            is_server_compiler,
+            dynamic_specifiers.clone(),
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("{file}: {}", err.kind().msg());
+            process::exit(2);
+        });
+
+        for call in calls.iter().filter(|call| call.differs()) {
+            has_diff = true;
+            println!("{file}: dynamic() call #{}", call.index);
+            for (mode, options) in &call.options {
+                println!("--- {mode}\n{options}");
+            }
+            println!();
+        }
+    }
+
+    process::exit(i32::from(has_diff));
+}
@@ -0,0 +1,411 @@
+//! Caches the output of the transform by content hash, so that unchanged
+//! modules don't need to be transformed again, e.g. in long-running
+//! development servers.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use swc_core::{
+    common::{
+        comments::SingleThreadedComments, sync::Lrc, BytePos, FileName, SourceFile, SourceMap, Span,
+    },
+    ecma::{
+        ast::EsVersion,
+        codegen::{text_writer::JsWriter, Emitter, Node},
+        parser::{error::Error, parse_file_as_module},
+        visit::FoldWith,
+    },
+};
+
+use crate::{
+    codemod::syntax, next_dynamic_patcher, rel_filename, Config, DynamicCall, DynamicDiagnostic,
+    ManifestEntry, NextDynamicMode,
+};
+
+/// The output of the transform of a module, along with its metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedTransform {
+    /// The generated code.
+    pub code: String,
+    /// The React Loadable Manifest entries generated by the module.
+    pub manifest_entries: Vec<ManifestEntry>,
+    /// The diagnostics reported for the module, which are replayed when the
+    /// output is reused.
+    #[serde(default)]
+    pub diagnostics: Vec<DynamicDiagnostic>,
+    /// The `dynamic()` calls of the module, for the `Config::dynamic_calls`.
+    #[serde(default)]
+    pub dynamic_calls: Vec<DynamicCall>,
+    /// The React Loadable Manifest keys generated by the module, registered in
+    /// the `Config::key_registry` again when the output is reused.
+    #[serde(default)]
+    pub keys: Vec<CachedKey>,
+}
+
+/// A React Loadable Manifest key generated by a `dynamic()` call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedKey {
+    pub key: String,
+    /// The specifier imported by the `dynamic()` call.
+    pub specifier: String,
+    /// The span of the `dynamic()` call.
+    pub span: Span,
+}
+
+/// Stores the outputs of the transform by [`cache_key`].
+pub trait TransformCache: Send + Sync {
+    fn get(&self, key: u64) -> Option<CachedTransform>;
+    fn set(&self, key: u64, output: CachedTransform);
+}
+
+/// A [`TransformCache`] storing outputs in memory.
+#[derive(Debug, Default)]
+pub struct MemoryTransformCache {
+    outputs: Mutex<HashMap<u64, CachedTransform>>,
+}
+
+impl MemoryTransformCache {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl TransformCache for MemoryTransformCache {
+    fn get(&self, key: u64) -> Option<CachedTransform> {
+        self.outputs.lock().unwrap().get(&key).cloned()
+    }
+
+    fn set(&self, key: u64, output: CachedTransform) {
+        self.outputs.lock().unwrap().insert(key, output);
+    }
+}
+
+/// Returns the cache key of the transform of `source`, which is only stable
+/// within a process, or `None` when its output can't be cached.
+///
+/// The key accounts for every input of the transform, including the mode, the
+/// pages directory and the options of `config`. The hooks of `config`, e.g.
+/// the `Config::chunk_resolver`, are compared by identity, so the same hooks
+/// must be passed for the outputs to be reused. Its sinks, e.g. the
+/// `Config::event_sink`, don't change the output.
+///
+/// The modes using a custom codegen are only cached when it has a
+/// [`DynamicCodegen::cache_key`](crate::DynamicCodegen::cache_key).
+#[allow(clippy::too_many_arguments)]
+pub fn cache_key(
+    source: &str,
+    filename: &FileName,
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: &NextDynamicMode,
+    pages_dir: Option<&Path>,
+    config: &Config,
+) -> Option<u64> {
+    let mode = match mode {
+        NextDynamicMode::Custom(codegen) => format!("Custom({})", codegen.cache_key()?),
+        mode => format!("{mode:?}"),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    filename.to_string().hash(&mut hasher);
+    is_development.hash(&mut hasher);
+    is_server_compiler.hash(&mut hasher);
+    is_react_server_layer.hash(&mut hasher);
+    mode.hash(&mut hasher);
+    pages_dir.hash(&mut hasher);
+    hash_config(config, &mut hasher);
+    Some(hasher.finish())
+}
+
+/// Hashes the options of `config`. It's destructured so that new options
+/// can't be forgotten.
+fn hash_config(config: &Config, hasher: &mut impl Hasher) {
+    let Config {
+        key_registry: _,
+        manifest_entries: _,
+        dynamic_calls: _,
+        module_id_resolver,
+        paths,
+        base_url,
+        root_dir,
+        transparent_wrappers,
+        dynamic_specifiers,
+        max_key_length,
+        minify_keys,
+        transform_generated,
+        env,
+        react_use,
+        react_compiler,
+        collect_diagnostics: _,
+        strict,
+        default_ssr,
+        webpack_resolver,
+        is_edge_entry,
+        non_streaming,
+        runtime,
+        eager_specifiers,
+        inline_specifiers,
+        test_stub,
+        chunk_resolver,
+        is_amp_only,
+        exclude,
+        babel_compat,
+        event_sink: _,
+        preserve_types,
+    } = config;
+
+    module_id_resolver
+        .as_ref()
+        .map(|hook| &*hook.0 as *const _)
+        .hash(hasher);
+    paths.hash(hasher);
+    base_url.hash(hasher);
+    root_dir.hash(hasher);
+    transparent_wrappers.hash(hasher);
+    dynamic_specifiers.hash(hasher);
+    max_key_length.hash(hasher);
+    minify_keys.hash(hasher);
+    transform_generated
+        .as_ref()
+        .map(|hook| &*hook.0 as *const _)
+        .hash(hasher);
+    // Sorted, as the iteration order of maps differs between instances.
+    let mut env = env.iter().collect::<Vec<_>>();
+    env.sort();
+    env.hash(hasher);
+    react_use.hash(hasher);
+    react_compiler.hash(hasher);
+    strict.hash(hasher);
+    default_ssr.hash(hasher);
+    webpack_resolver.hash(hasher);
+    is_edge_entry.hash(hasher);
+    non_streaming.hash(hasher);
+    runtime.hash(hasher);
+    eager_specifiers.hash(hasher);
+    inline_specifiers.hash(hasher);
+    test_stub.hash(hasher);
+    chunk_resolver
+        .as_ref()
+        .map(|hook| &*hook.0 as *const _)
+        .hash(hasher);
+    is_amp_only.hash(hasher);
+    exclude.hash(hasher);
+    babel_compat.hash(hasher);
+    preserve_types.hash(hasher);
+}
+
+/// Returns the cached output for `key`, or runs `transform` and caches its
+/// output.
+pub fn get_or_transform(
+    cache: &dyn TransformCache,
+    key: u64,
+    transform: impl FnOnce() -> CachedTransform,
+) -> CachedTransform {
+    if let Some(output) = cache.get(key) {
+        return output;
+    }
+
+    let output = transform();
+    cache.set(key, output.clone());
+    output
+}
+
+/// Transforms the module `fm` with
+/// [`next_dynamic_with_comments`](crate::next_dynamic_with_comments) and
+/// prints it along with its comments, unless its output is already in `cache`.
+///
+/// The metadata of the transform is replayed when the output is reused, as if
+/// the module was transformed again: the diagnostics are emitted to the
+/// thread-local `HANDLER`, unless `Config::collect_diagnostics` is set, the
+/// manifest entries and the `dynamic()` calls are added to the
+/// `Config::manifest_entries` and the `Config::dynamic_calls`, and the keys
+/// are registered in the `Config::key_registry`. The `Config::event_sink` is
+/// only notified when the module is transformed.
+///
+/// The spans of the returned diagnostics point into `fm`, which must be part
+/// of the `SourceMap` of the `HANDLER`. Like
+/// [`transform_batch`](crate::batch::transform_batch), the caller must set the
+/// swc `GLOBALS` on the current thread.
+#[allow(clippy::too_many_arguments)]
+pub fn transform_cached(
+    cache: &dyn TransformCache,
+    fm: &SourceFile,
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    pages_dir: Option<PathBuf>,
+    mut config: Config,
+) -> Result<CachedTransform, Error> {
+    let cache_key = cache_key(
+        &fm.src,
+        &fm.name,
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        &mode,
+        pages_dir.as_deref(),
+        &config,
+    );
+    let emits_diagnostics = !config.collect_diagnostics;
+    let manifest_entries = config.manifest_entries.take();
+    let dynamic_calls = config.dynamic_calls.take();
+    let key_registry = config.key_registry.take();
+    let filename = rel_filename(pages_dir.as_deref(), &fm.name);
+
+    let cached = cache_key.and_then(|key| cache.get(key));
+    let mut output = match cached {
+        Some(output) => output,
+        None => {
+            let output = transform(
+                fm,
+                is_development,
+                is_server_compiler,
+                is_react_server_layer,
+                mode,
+                pages_dir,
+                config,
+            )?;
+            if let Some(key) = cache_key {
+                cache.set(key, output.clone());
+            }
+            output
+        }
+    };
+
+    // The spans are cached relative to the module.
+    rebase(&mut output, BytePos(0), fm.start_pos);
+    if let Some(key_registry) = key_registry {
+        for CachedKey {
+            key,
+            specifier,
+            span,
+        } in &output.keys
+        {
+            if let Some(mut diagnostic) = key_registry.check(key, &fm.name, specifier, *span) {
+                diagnostic.filename = Some(filename.clone());
+                output.diagnostics.push(diagnostic);
+            }
+        }
+    }
+    if emits_diagnostics {
+        for diagnostic in &output.diagnostics {
+            diagnostic.emit();
+        }
+    }
+    if let Some(manifest_entries) = manifest_entries {
+        manifest_entries
+            .lock()
+            .unwrap()
+            .extend(output.manifest_entries.iter().cloned());
+    }
+    if let Some(dynamic_calls) = dynamic_calls {
+        dynamic_calls
+            .lock()
+            .unwrap()
+            .extend(output.dynamic_calls.iter().cloned());
+    }
+    Ok(output)
+}
+
+/// Transforms and prints `fm`, collecting its metadata to be cached.
+fn transform(
+    fm: &SourceFile,
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    pages_dir: Option<PathBuf>,
+    mut config: Config,
+) -> Result<CachedTransform, Error> {
+    let manifest_entries: Arc<Mutex<Vec<ManifestEntry>>> = Default::default();
+    let dynamic_calls: Arc<Mutex<Vec<DynamicCall>>> = Default::default();
+    config.manifest_entries = Some(manifest_entries.clone());
+    config.dynamic_calls = Some(dynamic_calls.clone());
+    config.collect_diagnostics = true;
+
+    let comments = SingleThreadedComments::default();
+    let module = parse_file_as_module(
+        fm,
+        syntax(&fm.name),
+        EsVersion::latest(),
+        Some(&comments),
+        &mut vec![],
+    )?;
+    let mut patcher = next_dynamic_patcher(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        mode,
+        fm.name.clone(),
+        pages_dir,
+        config,
+    );
+    patcher.emit_magic_comments = true;
+    patcher.registered_keys = Some(vec![]);
+    let module = module.fold_with(&mut patcher);
+    patcher.add_magic_comments(&comments);
+    let keys = patcher
+        .registered_keys
+        .take()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, specifier, span)| CachedKey {
+            key,
+            specifier,
+            span,
+        })
+        .collect();
+    let (Ok(diagnostics) | Err(diagnostics)) = patcher.finish();
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let mut code = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: Some(&comments),
+            wr: JsWriter::new(cm, "\n", &mut code, None),
+        };
+        module.emit_with(&mut emitter).unwrap();
+    }
+
+    let mut output = CachedTransform {
+        code: String::from_utf8(code).unwrap(),
+        manifest_entries: std::mem::take(&mut *manifest_entries.lock().unwrap()),
+        diagnostics,
+        dynamic_calls: std::mem::take(&mut *dynamic_calls.lock().unwrap()),
+        keys,
+    };
+    rebase(&mut output, fm.start_pos, BytePos(0));
+    Ok(output)
+}
+
+/// Moves the spans of `output` from a module starting at `from` to the same
+/// module starting at `to`.
+fn rebase(output: &mut CachedTransform, from: BytePos, to: BytePos) {
+    let rebase = |span: &mut Span| {
+        if !span.is_dummy() {
+            span.lo = BytePos(span.lo.0 - from.0 + to.0);
+            span.hi = BytePos(span.hi.0 - from.0 + to.0);
+        }
+    };
+    for diagnostic in &mut output.diagnostics {
+        rebase(&mut diagnostic.span);
+        for (span, _) in &mut diagnostic.labels {
+            rebase(span);
+        }
+    }
+    for key in &mut output.keys {
+        rebase(&mut key.span);
+    }
+}
@@ -0,0 +1,215 @@
+//! Helpers constructing the `loadableGenerated` option of `dynamic()` calls,
+//! for tools generating or post-processing code consistent with the
+//! transform.
+
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            ArrayLit, ArrowExpr, BlockStmtOrExpr, Expr, ExprOrSpread, Ident, KeyValueProp,
+            MemberExpr, MemberProp, ObjectLit, Prop, PropName, PropOrSpread,
+        },
+        utils::ExprFactory,
+    },
+};
+
+use crate::WebpackResolver;
+
+/// Builds the `loadableGenerated` option of a `dynamic()` call.
+#[derive(Debug, Default, Clone)]
+pub struct LoadableGenerated {
+    props: Vec<PropOrSpread>,
+}
+
+impl LoadableGenerated {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `module_id` to the `modules` option.
+    pub fn module_id(self, module_id: Expr) -> Self {
+        self.extend(module_id_options(module_id))
+    }
+
+    /// Sets the `webpack` option to a function returning `module_id`.
+    pub fn webpack(self, module_id: Expr) -> Self {
+        self.extend(webpack_options(module_id))
+    }
+
+    /// Adds options, merging their `modules` with the existing ones.
+    pub fn extend(self, props: Vec<PropOrSpread>) -> Self {
+        LoadableGenerated {
+            props: merge_module_id_options(self.props, props),
+        }
+    }
+
+    pub fn props(&self) -> &[PropOrSpread] {
+        &self.props
+    }
+
+    pub fn build(self) -> ObjectLit {
+        ObjectLit {
+            span: DUMMY_SP,
+            props: self.props,
+        }
+    }
+
+    /// Returns the `loadableGenerated: { ... }` property of the options of a
+    /// `dynamic()` call.
+    pub fn into_prop(self) -> PropOrSpread {
+        loadable_generated_prop(self.build())
+    }
+}
+
+/// Returns the `loadableGenerated: options` property of the options of a
+/// `dynamic()` call.
+pub fn loadable_generated_prop(options: ObjectLit) -> PropOrSpread {
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("loadableGenerated".into(), DUMMY_SP)),
+        value: Box::new(Expr::Object(options)),
+    })))
+}
+
+/// Returns the key of the generated `modules` or `webpack` option.
+pub(crate) fn loadable_key(props: &[PropOrSpread]) -> Option<Expr> {
+    let Some(PropOrSpread::Prop(prop)) = props.first() else {
+        return None;
+    };
+    let Prop::KeyValue(KeyValueProp { value, .. }) = &**prop else {
+        return None;
+    };
+    let elems = match &**value {
+        Expr::Array(ArrayLit { elems, .. }) => elems,
+        Expr::Arrow(ArrowExpr { body, .. }) => match &**body {
+            BlockStmtOrExpr::Expr(body) => match &**body {
+                Expr::Array(ArrayLit { elems, .. }) => elems,
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    elems.first()?.as_ref().map(|elem| (*elem.expr).clone())
+}
+
+/// Returns the 8 characters base36 hash used in place of `key` in the
+/// `modules` option, e.g. for keys longer than `Config::max_key_length`.
+pub fn hashed_key(key: &str) -> String {
+    // FNV-1a, which unlike `DefaultHasher` is stable across Rust versions, as
+    // the manifest writer needs to generate the same keys.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in key.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    let mut hash = hash % 36u64.pow(8);
+    let mut digits = [b'0'; 8];
+    for digit in digits.iter_mut().rev() {
+        *digit = b"0123456789abcdefghijklmnopqrstuvwxyz"[(hash % 36) as usize];
+        hash /= 36;
+    }
+    String::from_utf8(digits.to_vec()).unwrap()
+}
+
+/// Returns the `modules: [module_id]` option.
+pub fn module_id_options(module_id: Expr) -> Vec<PropOrSpread> {
+    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("modules".into(), DUMMY_SP)),
+        value: Box::new(Expr::Array(ArrayLit {
+            elems: vec![Some(ExprOrSpread {
+                expr: Box::new(module_id),
+                spread: None,
+            })],
+            span: DUMMY_SP,
+        })),
+    })))]
+}
+
+/// Returns the `modules: [], error: reason` options generated in place of the
+/// options of an invalid `dynamic()` call, so that `next/dynamic` logs `reason`
+/// in the browser console when the component is created in development.
+pub fn placeholder_options(reason: &str) -> Vec<PropOrSpread> {
+    vec![
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident::new("modules".into(), DUMMY_SP)),
+            value: Box::new(Expr::Array(ArrayLit {
+                elems: vec![],
+                span: DUMMY_SP,
+            })),
+        }))),
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident::new("error".into(), DUMMY_SP)),
+            value: Box::new(reason.into()),
+        }))),
+    ]
+}
+
+/// Appends the module ids of `other` to the `modules` option of `options`, or
+/// adds the `modules` option of `other` when `options` has none.
+pub fn merge_module_id_options(
+    mut options: Vec<PropOrSpread>,
+    mut other: Vec<PropOrSpread>,
+) -> Vec<PropOrSpread> {
+    if let (Some(modules), Some(other_modules)) =
+        (module_ids_mut(&mut options), module_ids_mut(&mut other))
+    {
+        modules.append(other_modules);
+        return options;
+    }
+
+    options.append(&mut other);
+    options
+}
+
+/// Returns the elements of the `modules` option generated by
+/// `module_id_options`.
+fn module_ids_mut(options: &mut [PropOrSpread]) -> Option<&mut Vec<Option<ExprOrSpread>>> {
+    options.iter_mut().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &mut **prop {
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(key),
+                value,
+            }) if &*key.sym == "modules" => match &mut **value {
+                Expr::Array(ArrayLit { elems, .. }) => Some(elems),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Returns `require.resolveWeak(module_id)`, or the expression of another
+/// Webpack `resolver`.
+pub fn resolve_module_id(resolver: WebpackResolver, module_id: Expr) -> Expr {
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(Ident::new("require".into(), DUMMY_SP))),
+        prop: MemberProp::Ident(Ident::new(resolver.as_str().into(), DUMMY_SP)),
+    })
+    .as_call(DUMMY_SP, vec![module_id.as_arg()])
+}
+
+/// Returns the `webpack: () => [module_id]` option.
+pub fn webpack_options(module_id: Expr) -> Vec<PropOrSpread> {
+    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("webpack".into(), DUMMY_SP)),
+        value: Box::new(Expr::Arrow(ArrowExpr {
+            params: vec![],
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Array(ArrayLit {
+                elems: vec![Some(ExprOrSpread {
+                    expr: Box::new(module_id),
+                    spread: None,
+                })],
+                span: DUMMY_SP,
+            })))),
+            is_async: false,
+            is_generator: false,
+            span: DUMMY_SP,
+            return_type: None,
+            type_params: None,
+        })),
+    })))]
+}
@@ -0,0 +1,329 @@
+//! Rewrites the `dynamic()` calls of a module for `next codemod`. Unlike the
+//! transform, the rewritten source is meant to be committed, so the calls are
+//! edited in the original source text, and the rest of the module, including
+//! its formatting and comments, is left byte for byte as it was.
+
+use swc_core::{
+    common::{sync::Lrc, BytePos, FileName, SourceMap, Span, Spanned},
+    ecma::{
+        ast::{
+            CallExpr, Callee, EsVersion, Expr, Id, ImportDecl, ImportSpecifier, KeyValueProp,
+            ObjectLit, Pat, Prop, PropOrSpread, VarDeclarator,
+        },
+        parser::{error::Error, parse_file_as_module, EsConfig, Syntax, TsConfig},
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+use crate::{interop, is_key_value_prop, options_object, Config};
+
+/// A rewrite applied by [`codemod`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Rewrite {
+    /// Converts the legacy `dynamic({ loader, ...options })` form to
+    /// `dynamic(loader, options)`.
+    LegacyObjectForm,
+    /// Removes the `loadableGenerated` options accidentally committed along
+    /// with compiled output, so that the transform generates them again from
+    /// the current module ids.
+    StaleLoadableGenerated,
+}
+
+/// Applies `rewrites` to the `dynamic()` calls of `source` and returns the
+/// rewritten source, or `None` when no call was rewritten, so that the file
+/// can be left untouched.
+///
+/// `dynamic` is imported or required from the `Config::dynamic_specifiers`,
+/// the other options of `config` being unused.
+pub fn codemod(
+    source: &str,
+    filename: FileName,
+    config: &Config,
+    rewrites: &[Rewrite],
+) -> Result<Option<String>, Error> {
+    let syntax = syntax(&filename);
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(filename, source.into());
+    let module = parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![])?;
+
+    let mut rewriter = Rewriter {
+        config,
+        rewrites,
+        source,
+        start_pos: fm.start_pos,
+        dynamic_bindings: vec![],
+        edits: vec![],
+    };
+    module.visit_with(&mut rewriter);
+    if rewriter.edits.is_empty() {
+        return Ok(None);
+    }
+
+    // Applied from the end of the source so that the offsets of the remaining
+    // edits stay valid. An insertion is applied after a removal starting at
+    // the same offset, so that it ends up before the text following it.
+    let mut edits = rewriter.edits;
+    edits.sort_by_key(|edit| (edit.lo, edit.hi));
+    let mut output = source.to_string();
+    for edit in edits.into_iter().rev() {
+        output.replace_range(edit.lo..edit.hi, &edit.text);
+    }
+    Ok(Some(output))
+}
+
+/// Returns the syntax of the module from its extension, as codemods run on
+/// sources rather than on the output of other transforms.
+pub(crate) fn syntax(filename: &FileName) -> Syntax {
+    let name = filename.to_string();
+    if name.ends_with(".ts") || name.ends_with(".mts") || name.ends_with(".cts") {
+        Syntax::Typescript(Default::default())
+    } else if name.ends_with(".tsx") {
+        Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        })
+    } else {
+        Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        })
+    }
+}
+
+/// Replaces the bytes `lo..hi` of the source with `text`.
+struct Edit {
+    lo: usize,
+    hi: usize,
+    text: String,
+}
+
+struct Rewriter<'a> {
+    config: &'a Config,
+    rewrites: &'a [Rewrite],
+    source: &'a str,
+    start_pos: BytePos,
+    dynamic_bindings: Vec<Id>,
+    edits: Vec<Edit>,
+}
+
+impl Visit for Rewriter<'_> {
+    noop_visit_type!();
+
+    fn visit_import_decl(&mut self, decl: &ImportDecl) {
+        if decl.type_only || !self.config.is_dynamic_specifier(&decl.src.value) {
+            return;
+        }
+
+        for specifier in &decl.specifiers {
+            if let ImportSpecifier::Default(specifier) = specifier {
+                self.dynamic_bindings.push(specifier.local.to_id());
+            }
+        }
+    }
+
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        decl.visit_children_with(self);
+
+        if let (Pat::Ident(name), Some(Expr::Call(init))) = (&decl.name, decl.init.as_deref()) {
+            if interop::require_specifier(init)
+                .is_some_and(|specifier| self.config.is_dynamic_specifier(&specifier.value))
+            {
+                self.dynamic_bindings.push(name.id.to_id());
+            }
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // The arguments of a rewritten call may be moved as is, so the calls
+        // they contain are left untouched rather than edited twice.
+        if !self.is_dynamic_call(call) || !self.rewrite(call) {
+            call.visit_children_with(self);
+        }
+    }
+}
+
+impl<'a> Rewriter<'a> {
+    fn is_dynamic_call(&self, call: &CallExpr) -> bool {
+        match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(callee) => self.dynamic_bindings.contains(&callee.to_id()),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Applies the `rewrites` to `call`. The `loadableGenerated` options of a
+    /// migrated call are stripped along with its migration, as both edit the
+    /// same options.
+    fn rewrite(&mut self, call: &CallExpr) -> bool {
+        let strip = self.rewrites.contains(&Rewrite::StaleLoadableGenerated);
+        (self.rewrites.contains(&Rewrite::LegacyObjectForm)
+            && self.migrate_legacy_object_form(call, strip))
+            || (strip && self.strip_loadable_generated(call))
+    }
+
+    /// Rewrites `dynamic({ loader, ...options }, extraOptions)` to
+    /// `dynamic(loader, { ...options, ...extraOptions })`, the options being
+    /// omitted when empty. Calls with spread arguments, or whose second
+    /// argument isn't an object literal, are left untouched, as their options
+    /// can't be merged. The `loadableGenerated` options are removed when
+    /// `strip` is set.
+    fn migrate_legacy_object_form(&mut self, call: &CallExpr, strip: bool) -> bool {
+        if call.args.is_empty()
+            || call.args.len() > 2
+            || call.args.iter().any(|arg| arg.spread.is_some())
+        {
+            return false;
+        }
+        let extra_options = match call.args.get(1).map(|arg| &*arg.expr) {
+            Some(Expr::Object(extra_options)) => Some(extra_options),
+            Some(..) => return false,
+            None => None,
+        };
+        let Expr::Object(legacy_options) = &*call.args[0].expr else {
+            return false;
+        };
+        let Some(loader) = legacy_options
+            .props
+            .iter()
+            .find(|prop| is_key_value_prop(prop, "loader"))
+        else {
+            return false;
+        };
+        let PropOrSpread::Prop(loader) = loader else {
+            unreachable!();
+        };
+        let Prop::KeyValue(KeyValueProp { value: loader, .. }) = &**loader else {
+            unreachable!();
+        };
+        let loader = self.text(loader.span());
+
+        let is_stale = |prop: &PropOrSpread| strip && is_key_value_prop(prop, "loadableGenerated");
+        let is_removed = |prop: &PropOrSpread| is_key_value_prop(prop, "loader") || is_stale(prop);
+        let kept_options = legacy_options
+            .props
+            .iter()
+            .rev()
+            .find(|prop| !is_removed(prop));
+        let extra_props = extra_options.map_or(vec![], |extra_options| {
+            extra_options
+                .props
+                .iter()
+                .filter(|prop| !is_stale(prop))
+                .collect()
+        });
+
+        match (kept_options, extra_options) {
+            // `dynamic({ loader }, { ...extraOptions })`
+            (None, Some(extra_options)) if !extra_props.is_empty() => {
+                self.replace(
+                    legacy_options.span.lo,
+                    extra_options.span.lo,
+                    format!("{loader}, "),
+                );
+                self.remove_props(extra_options, is_stale);
+            }
+            // `dynamic({ loader })` or `dynamic({ loader }, {})`
+            (None, _) => {
+                let hi = call.args.last().unwrap().expr.span().hi;
+                self.replace(legacy_options.span.lo, hi, loader);
+            }
+            (Some(last_kept_option), extra_options) => {
+                self.replace(
+                    legacy_options.span.lo,
+                    legacy_options.span.lo,
+                    format!("{loader}, "),
+                );
+                self.remove_props(legacy_options, is_removed);
+
+                if let Some(extra_options) = extra_options {
+                    if !extra_props.is_empty() {
+                        let extra_props = extra_props
+                            .iter()
+                            .map(|prop| self.text(prop.span()))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let hi = last_kept_option.span().hi;
+                        self.replace(hi, hi, format!(", {extra_props}"));
+                    }
+                    self.replace(legacy_options.span.hi, extra_options.span.hi, "");
+                }
+            }
+        }
+        true
+    }
+
+    /// Removes the `loadableGenerated` options of `call`, along with its
+    /// options argument when nothing else is left in it.
+    fn strip_loadable_generated(&mut self, call: &CallExpr) -> bool {
+        let is_stale = |prop: &PropOrSpread| is_key_value_prop(prop, "loadableGenerated");
+
+        let mut stripped = false;
+        for (index, arg) in call.args.iter().enumerate() {
+            let Some(options) = options_object(&arg.expr) else {
+                continue;
+            };
+            if index == 1 && !options.props.is_empty() && options.props.iter().all(is_stale) {
+                self.replace(call.args[0].expr.span().hi, arg.expr.span().hi, "");
+                stripped = true;
+            } else {
+                stripped |= self.remove_props(options, is_stale);
+            }
+        }
+        stripped
+    }
+
+    /// Removes the props of `object` matching `is_removed`, along with the
+    /// commas separating them from the others. The object is replaced with
+    /// `{}` when all of its props are removed.
+    fn remove_props(
+        &mut self,
+        object: &ObjectLit,
+        is_removed: impl Fn(&PropOrSpread) -> bool,
+    ) -> bool {
+        let props = &object.props;
+        let mut removed = false;
+        let mut index = 0;
+        while index < props.len() {
+            if !is_removed(&props[index]) {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            while index < props.len() && is_removed(&props[index]) {
+                index += 1;
+            }
+            match (start, props.get(index)) {
+                // Up to the next prop, which keeps the following separator.
+                (_, Some(next)) => self.replace(props[start].span().lo, next.span().lo, ""),
+                (0, None) => self.replace(object.span.lo, object.span.hi, "{}"),
+                // From the previous prop, keeping the trailing comma if any.
+                (_, None) => {
+                    self.replace(props[start - 1].span().hi, props[index - 1].span().hi, "")
+                }
+            }
+            removed = true;
+        }
+        removed
+    }
+
+    /// Returns the source text of `span`.
+    fn text(&self, span: Span) -> &'a str {
+        &self.source[self.offset(span.lo)..self.offset(span.hi)]
+    }
+
+    fn offset(&self, pos: BytePos) -> usize {
+        (pos.0 - self.start_pos.0) as usize
+    }
+
+    fn replace(&mut self, lo: BytePos, hi: BytePos, text: impl Into<String>) {
+        self.edits.push(Edit {
+            lo: self.offset(lo),
+            hi: self.offset(hi),
+            text: text.into(),
+        });
+    }
+}
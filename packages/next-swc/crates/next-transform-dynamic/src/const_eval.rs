@@ -0,0 +1,167 @@
+//! Evaluates the expressions known at compile time, like the `ssr` option of
+//! `dynamic()` calls or the conditions around them, with a map of definitions
+//! similar to webpack's `DefinePlugin`.
+
+use std::{collections::HashMap, mem};
+
+use swc_core::{
+    common::collections::AHashSet,
+    ecma::{
+        ast::{
+            BinExpr, BinaryOp, CallExpr, Callee, Expr, ExprOrSpread, Id, Ident, Lit, MemberExpr,
+            MemberProp, UnaryExpr, UnaryOp,
+        },
+        utils::{collect_decls, BindingCollector},
+        visit::VisitWith,
+    },
+};
+
+/// A value statically known at compile time.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Undefined,
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(value) => *value,
+            Value::Num(value) => *value != 0.0 && !value.is_nan(),
+            Value::Str(value) => !value.is_empty(),
+            Value::Undefined => false,
+        }
+    }
+}
+
+/// Evaluates `expr` to a boolean, replacing `process.env.*` references with
+/// the values of `env`. Like with webpack's `DefinePlugin`, `typeof` checks
+/// are replaced with the values of keys like `typeof window`.
+///
+/// Returns `None` when the expression isn't statically known, including when
+/// it references environment variables missing from `env`, as they could
+/// still be defined at runtime.
+///
+/// The globals it references, e.g. `undefined` or `process`, aren't evaluated
+/// when they're shadowed by one of the `declared` bindings, as returned by
+/// [`declared_bindings`]. Without the `resolver`, a binding with the same name
+/// declared anywhere in the module shadows them.
+pub fn eval_bool(
+    expr: &Expr,
+    env: &HashMap<String, String>,
+    declared: &AHashSet<Id>,
+) -> Option<bool> {
+    let evaluator = Evaluator { env, declared };
+    match evaluator.eval(expr)? {
+        Value::Bool(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Returns the bindings declared in `node`, e.g. a module, for [`eval_bool`].
+pub fn declared_bindings<N>(node: &N) -> AHashSet<Id>
+where
+    N: VisitWith<BindingCollector<Id>>,
+{
+    collect_decls(node)
+}
+
+struct Evaluator<'a> {
+    env: &'a HashMap<String, String>,
+    declared: &'a AHashSet<Id>,
+}
+
+impl Evaluator<'_> {
+    fn eval(&self, expr: &Expr) -> Option<Value> {
+        match expr {
+            Expr::Lit(Lit::Bool(value)) => Some(Value::Bool(value.value)),
+            Expr::Lit(Lit::Num(value)) => Some(Value::Num(value.value)),
+            Expr::Lit(Lit::Str(value)) => Some(Value::Str(value.value.to_string())),
+            Expr::Ident(ident) if self.is_global(ident, "undefined") => Some(Value::Undefined),
+            Expr::Paren(paren) => self.eval(&paren.expr),
+            Expr::Member(member) => {
+                let name = self.process_env_name(member)?;
+                self.env.get(name).map(|value| Value::Str(value.clone()))
+            }
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::Bang,
+                arg,
+                ..
+            }) => Some(Value::Bool(!self.eval(arg)?.is_truthy())),
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::TypeOf,
+                arg,
+                ..
+            }) => match &**arg {
+                Expr::Ident(ident) if self.is_global(ident, &ident.sym) => self
+                    .env
+                    .get(&format!("typeof {}", ident.sym))
+                    .map(|value| Value::Str(value.clone())),
+                _ => None,
+            },
+            // `Boolean(value)`, as emitted by some compilers for `!!value`.
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) => match (&**callee, &args[..]) {
+                (Expr::Ident(callee), [ExprOrSpread { spread: None, expr }])
+                    if self.is_global(callee, "Boolean") =>
+                {
+                    Some(Value::Bool(self.eval(expr)?.is_truthy()))
+                }
+                _ => None,
+            },
+            Expr::Bin(BinExpr {
+                op, left, right, ..
+            }) => {
+                let (is_strict, equals) = match op {
+                    BinaryOp::EqEqEq => (true, true),
+                    BinaryOp::NotEqEq => (true, false),
+                    BinaryOp::EqEq => (false, true),
+                    BinaryOp::NotEq => (false, false),
+                    _ => return None,
+                };
+                let left = self.eval(left)?;
+                let right = self.eval(right)?;
+                // Loosely comparing values of different types converts them, e.g.
+                // `process.env.FLAG == 1` is true for `"1"`, and `true == "1"` is
+                // true too. Values of different types are never strictly equal.
+                if !is_strict && mem::discriminant(&left) != mem::discriminant(&right) {
+                    return None;
+                }
+                Some(Value::Bool((left == right) == equals))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether `ident` is the global `name`, rather than a binding
+    /// shadowing it.
+    fn is_global(&self, ident: &Ident, name: &str) -> bool {
+        &*ident.sym == name && !self.declared.contains(&ident.to_id())
+    }
+
+    /// Returns `NAME` for `process.env.NAME` member expressions.
+    fn process_env_name<'a>(&self, member: &'a MemberExpr) -> Option<&'a str> {
+        let Expr::Member(MemberExpr {
+            obj: process,
+            prop: MemberProp::Ident(env),
+            ..
+        }) = &*member.obj
+        else {
+            return None;
+        };
+
+        match (&**process, &member.prop) {
+            (Expr::Ident(process), MemberProp::Ident(name))
+                if self.is_global(process, "process") && &*env.sym == "env" =>
+            {
+                Some(&*name.sym)
+            }
+            _ => None,
+        }
+    }
+}
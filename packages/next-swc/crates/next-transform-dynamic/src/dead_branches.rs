@@ -0,0 +1,160 @@
+//! Removes the branches of conditions known at compile time, so that the
+//! `dynamic()` calls they contain don't generate manifest entries for code
+//! which never runs. Must run before the `next/dynamic` transform.
+//!
+//! The `var` declarations of the removed branches are kept without their
+//! initializers, as their bindings are hoisted out of the branches.
+
+use std::collections::HashMap;
+
+use swc_core::{
+    common::{collections::AHashSet, util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{
+            BlockStmt, Class, CondExpr, Decl, EmptyStmt, Expr, Function, Id, Ident, IfStmt, Module,
+            Pat, Script, Stmt, VarDecl, VarDeclKind, VarDeclarator,
+        },
+        utils::find_pat_ids,
+        visit::{
+            as_folder, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitMutWith,
+            VisitWith,
+        },
+    },
+};
+
+use crate::const_eval;
+
+/// Creates a SWC visitor replacing `if` statements and conditional expressions
+/// with their taken branch, when their condition can be evaluated with `env`.
+///
+/// `env` contains the values of `process.env.*` variables (e.g. `NODE_ENV`),
+/// along with `typeof` checks like `typeof window`.
+pub fn eliminate_dead_branches(env: HashMap<String, String>) -> impl Fold {
+    as_folder(DeadBranches {
+        env,
+        declared: Default::default(),
+    })
+}
+
+struct DeadBranches {
+    env: HashMap<String, String>,
+    /// The bindings declared in the module, which may shadow the globals of
+    /// the conditions.
+    declared: AHashSet<Id>,
+}
+
+impl VisitMut for DeadBranches {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        self.declared = const_eval::declared_bindings(module);
+        module.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_script(&mut self, script: &mut Script) {
+        self.declared = const_eval::declared_bindings(script);
+        script.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        stmt.visit_mut_children_with(self);
+
+        let Stmt::If(IfStmt {
+            test, cons, alt, ..
+        }) = stmt
+        else {
+            return;
+        };
+        let Some(test) = const_eval::eval_bool(test, &self.env, &self.declared) else {
+            return;
+        };
+
+        let (taken, removed) = if test {
+            (Some(cons.take()), alt.take())
+        } else {
+            (alt.take(), Some(cons.take()))
+        };
+        let hoisted = removed.and_then(|removed| hoisted_vars(&removed));
+
+        *stmt = match (taken, hoisted) {
+            (Some(taken), Some(hoisted)) => match *taken {
+                Stmt::Block(mut block) => {
+                    block.stmts.push(hoisted);
+                    Stmt::Block(block)
+                }
+                taken => Stmt::Block(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![taken, hoisted],
+                }),
+            },
+            (Some(taken), None) => *taken,
+            (None, Some(hoisted)) => hoisted,
+            (None, None) => Stmt::Empty(EmptyStmt { span: DUMMY_SP }),
+        };
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        expr.visit_mut_children_with(self);
+
+        let Expr::Cond(CondExpr {
+            test, cons, alt, ..
+        }) = expr
+        else {
+            return;
+        };
+        let Some(test) = const_eval::eval_bool(test, &self.env, &self.declared) else {
+            return;
+        };
+
+        *expr = if test { *cons.take() } else { *alt.take() };
+    }
+}
+
+/// Returns the declaration, without initializers, of the `var` bindings of the
+/// removed branch `stmt`, if any.
+fn hoisted_vars(stmt: &Stmt) -> Option<Stmt> {
+    let mut collector = VarCollector { vars: vec![] };
+    stmt.visit_with(&mut collector);
+    if collector.vars.is_empty() {
+        return None;
+    }
+
+    Some(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: collector
+            .vars
+            .into_iter()
+            .map(|var| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(var.into()),
+                init: None,
+                definite: false,
+            })
+            .collect(),
+    }))))
+}
+
+/// Collects the bindings of the `var` declarations of a statement, outside of
+/// the functions and classes it contains.
+struct VarCollector {
+    vars: Vec<Ident>,
+}
+
+impl Visit for VarCollector {
+    noop_visit_type!();
+
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        if decl.kind == VarDeclKind::Var {
+            for declarator in &decl.decls {
+                self.vars.extend(find_pat_ids::<_, Ident>(&declarator.name));
+            }
+        }
+        decl.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
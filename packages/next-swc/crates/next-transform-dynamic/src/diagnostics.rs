@@ -0,0 +1,145 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use swc_core::common::{errors::HANDLER, SourceMap, Span};
+
+use crate::messages::{DiagnosticCode, Message};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+}
+
+/// A diagnostic reported by the `next/dynamic` transform.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicDiagnostic {
+    pub level: DiagnosticLevel,
+    pub span: Span,
+    /// The stable code of the diagnostic, which tools should match on rather
+    /// than on the message.
+    pub code: DiagnosticCode,
+    pub message: String,
+    /// Other spans shown with the diagnostic, with their label.
+    pub labels: Vec<(Span, String)>,
+    /// The file of the diagnostic, relative to the pages directory.
+    pub filename: Option<String>,
+    /// The specifier imported by the `dynamic()` call, when it's known.
+    pub specifier: Option<String>,
+}
+
+impl DynamicDiagnostic {
+    pub(crate) fn new(level: DiagnosticLevel, span: Span, message: Message) -> Self {
+        DynamicDiagnostic {
+            level,
+            span,
+            code: message.code(),
+            message: message.to_string(),
+            labels: vec![],
+            filename: None,
+            specifier: None,
+        }
+    }
+
+    /// Emits the diagnostic to the thread-local `HANDLER`, with its file and
+    /// specifier in the message, like it's displayed.
+    pub fn emit(&self) {
+        let message = self.to_string();
+        HANDLER.with(|handler| {
+            let mut builder = match self.level {
+                DiagnosticLevel::Error => handler.struct_span_err(self.span, &message),
+                DiagnosticLevel::Warning => handler.struct_span_warn(self.span, &message),
+            };
+            for (span, label) in &self.labels {
+                builder.span_label(*span, label);
+            }
+            builder.emit();
+        });
+    }
+
+    /// Resolves the span of the diagnostic in `cm`, the source map of the
+    /// module, so that the dev overlay can render a code frame without parsing
+    /// the file again.
+    ///
+    /// Returns `None` when the diagnostic has no span, e.g. when it's reported
+    /// for generated code, or when its span isn't part of `cm`.
+    pub fn to_overlay(&self, cm: &SourceMap) -> Option<OverlayDiagnostic> {
+        if self.span.is_dummy() {
+            return None;
+        }
+        let start = cm.try_lookup_char_pos(self.span.lo).ok()?;
+        let end = cm.try_lookup_char_pos(self.span.hi).ok()?;
+        let file = &start.file;
+
+        // The lines of the span, along with a line of context around them.
+        let first_line = start.line.saturating_sub(1).max(1);
+        let last_line = (end.line + 1).min(file.count_lines());
+        let snippet = (first_line..=last_line)
+            .filter_map(|line| file.get_line(line - 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(OverlayDiagnostic {
+            severity: self.level,
+            code: self.code.as_str(),
+            message: self.message.clone(),
+            filename: self.filename.clone(),
+            specifier: self.specifier.clone(),
+            start: (self.span.lo - file.start_pos).0,
+            end: (self.span.hi - file.start_pos).0,
+            line: start.line,
+            column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            snippet,
+            snippet_line: first_line,
+            docs_url: self.code.docs_url(),
+        })
+    }
+}
+
+/// A diagnostic resolved in the source of its module, in the shape expected
+/// by the Next.js dev overlay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayDiagnostic {
+    pub severity: DiagnosticLevel,
+    pub code: &'static str,
+    pub message: String,
+    pub filename: Option<String>,
+    pub specifier: Option<String>,
+    /// The byte offset of the start of the span in the file.
+    pub start: u32,
+    /// The byte offset of the end of the span in the file.
+    pub end: u32,
+    /// The 1-based line of the start of the span.
+    pub line: usize,
+    /// The 1-based column of the start of the span, in characters.
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// The lines of the span, along with a line of context before and after
+    /// them.
+    pub snippet: String,
+    /// The 1-based line of the first line of `snippet`.
+    pub snippet_line: usize,
+    pub docs_url: Option<&'static str>,
+}
+
+/// Formats the diagnostic with its file and specifier, for logs where its span
+/// is lost, e.g. aggregated CI logs.
+impl fmt::Display for DynamicDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(filename) = &self.filename {
+            write!(f, "{filename}: ")?;
+        }
+        f.write_str(&self.message)?;
+        if let Some(specifier) = &self.specifier {
+            write!(f, " (importing \"{specifier}\")")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! Names the components returned by `dynamic()` calls and the anonymous
+//! components after the variable or the member they are assigned to, so that
+//! React DevTools and error stacks show e.g. `DynamicHero` instead of
+//! `LoadableComponent` or `default`.
+
+use swc_core::{
+    ecma::{
+        ast::{
+            AssignOp, Callee, Decl, Expr, FnExpr, Id, MemberProp, ModuleDecl, ModuleItem, Pat,
+            PatOrExpr, Stmt, VarDecl,
+        },
+        visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith},
+    },
+    quote,
+};
+
+use crate::Config;
+
+/// Creates a SWC visitor adding a `displayName` to the components assigned to
+/// a variable or a member, in any scope and by any declaration or assignment:
+/// - the components returned by `dynamic()` calls,
+/// - the anonymous arrow and function components, i.e. assigned to a
+///   capitalized name, including the ones generated in place of `dynamic()`
+///   calls when this visitor runs after the transform, e.g. with
+///   `Config::test_stub`.
+///
+/// `dynamic` is imported from the `Config::dynamic_specifiers`, the other
+/// options of `config` being unused.
+pub fn display_names(config: Config) -> impl Fold {
+    as_folder(DisplayNames {
+        config,
+        dynamic_bindings: vec![],
+    })
+}
+
+struct DisplayNames {
+    config: Config,
+    dynamic_bindings: Vec<Id>,
+}
+
+impl VisitMut for DisplayNames {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        for item in items.iter() {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                if !import.type_only && self.config.is_dynamic_specifier(&import.src.value) {
+                    self.dynamic_bindings.extend(
+                        import
+                            .specifiers
+                            .iter()
+                            .filter_map(|specifier| specifier.as_default())
+                            .map(|specifier| specifier.local.to_id()),
+                    );
+                }
+            }
+        }
+        items.visit_mut_children_with(self);
+
+        let mut new_items = Vec::with_capacity(items.len());
+        for item in items.drain(..) {
+            let display_names = match &item {
+                ModuleItem::Stmt(stmt) => self.display_names(stmt),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => export
+                    .decl
+                    .as_var()
+                    .map(|var| self.var_display_names(var))
+                    .unwrap_or_default(),
+                _ => vec![],
+            };
+
+            new_items.push(item);
+            new_items.extend(display_names.into_iter().map(ModuleItem::Stmt));
+        }
+        *items = new_items;
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        stmts.visit_mut_children_with(self);
+
+        let mut new_stmts = Vec::with_capacity(stmts.len());
+        for stmt in stmts.drain(..) {
+            let display_names = self.display_names(&stmt);
+            new_stmts.push(stmt);
+            new_stmts.extend(display_names);
+        }
+        *stmts = new_stmts;
+    }
+}
+
+impl DisplayNames {
+    /// Returns the `displayName` assignments of the components declared or
+    /// assigned by `stmt`.
+    fn display_names(&self, stmt: &Stmt) -> Vec<Stmt> {
+        match stmt {
+            Stmt::Decl(Decl::Var(var)) => self.var_display_names(var),
+            Stmt::Expr(stmt) => {
+                let Expr::Assign(assign) = &*stmt.expr else {
+                    return vec![];
+                };
+                if assign.op != AssignOp::Assign {
+                    return vec![];
+                }
+                let target = match &assign.left {
+                    PatOrExpr::Pat(pat) => match &**pat {
+                        Pat::Ident(name) => Some(Expr::Ident(name.id.clone())),
+                        Pat::Expr(expr) => Some((**expr).clone()),
+                        _ => None,
+                    },
+                    PatOrExpr::Expr(expr) => Some((**expr).clone()),
+                };
+                target
+                    .and_then(|target| self.display_name(target, &assign.right))
+                    .into_iter()
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns the `displayName` assignments of the components declared by
+    /// `var`.
+    fn var_display_names(&self, var: &VarDecl) -> Vec<Stmt> {
+        var.decls
+            .iter()
+            .filter_map(|decl| {
+                let Pat::Ident(name) = &decl.name else {
+                    return None;
+                };
+                self.display_name(Expr::Ident(name.id.clone()), decl.init.as_deref()?)
+            })
+            .collect()
+    }
+
+    /// Returns the `displayName` assignment of `target`, an identifier or a
+    /// member, when `value` is a component.
+    fn display_name(&self, target: Expr, value: &Expr) -> Option<Stmt> {
+        let name = match &target {
+            Expr::Ident(ident) => ident.sym.clone(),
+            Expr::Member(member) => match &member.prop {
+                MemberProp::Ident(prop) => prop.sym.clone(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if !self.is_component(&name, value) {
+            return None;
+        }
+
+        Some(quote!(
+            "$target.displayName = $display_name;" as Stmt,
+            target: Expr = target,
+            display_name: Expr = name.to_string().into(),
+        ))
+    }
+
+    /// Returns whether `value`, assigned to `name`, is a `dynamic()` component
+    /// or an anonymous component.
+    fn is_component(&self, name: &str, value: &Expr) -> bool {
+        match value {
+            Expr::Paren(paren) => self.is_component(name, &paren.expr),
+            Expr::Call(call) => match &call.callee {
+                Callee::Expr(callee) => matches!(
+                    &**callee,
+                    Expr::Ident(callee) if self.dynamic_bindings.contains(&callee.to_id())
+                ),
+                _ => false,
+            },
+            Expr::Arrow(..) | Expr::Fn(FnExpr { ident: None, .. }) => {
+                name.starts_with(|c: char| c.is_ascii_uppercase())
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Transforms a module for both Webpack and Turbopack, e.g. for `next build`
+//! validating the output of both bundlers, with a single traversal of the
+//! transform.
+
+use std::{path::Path, sync::Arc};
+
+use swc_core::{
+    common::{errors::HANDLER, FileName, Mark, SyntaxContext},
+    ecma::{
+        ast::{
+            CallExpr, Expr, Id, ImportSpecifier, ModuleDecl, ModuleItem, ObjectLit, Program, Prop,
+            PropOrSpread, Str,
+        },
+        visit::{
+            noop_visit_mut_type, noop_visit_type, FoldWith, Visit, VisitMut, VisitMutWith,
+            VisitWith,
+        },
+    },
+};
+
+use crate::{
+    batch::TransformResult, is_key_value_prop, next_dynamic_patcher_shared, options_object_mut,
+    Config, DiagnosticCode, DynamicDiagnostic, NextDynamicMode, NextDynamicPatcher,
+};
+
+/// The outputs of [`transform_dual`].
+#[derive(Debug, Clone)]
+pub enum DualOutput {
+    /// The module doesn't reference `next/dynamic`, so it's left untouched
+    /// and shared by both backends.
+    Shared(Program),
+    /// The module references `next/dynamic`, and was transformed for each
+    /// backend.
+    Split {
+        webpack: Box<TransformResult>,
+        turbopack: Box<TransformResult>,
+    },
+}
+
+impl DualOutput {
+    /// Returns the module transformed for Webpack.
+    pub fn webpack(&self) -> &Program {
+        match self {
+            DualOutput::Shared(program) => program,
+            DualOutput::Split { webpack, .. } => &webpack.program,
+        }
+    }
+
+    /// Returns the module transformed for Turbopack.
+    pub fn turbopack(&self) -> &Program {
+        match self {
+            DualOutput::Shared(program) => program,
+            DualOutput::Split { turbopack, .. } => &turbopack.program,
+        }
+    }
+}
+
+/// Transforms `program` in Webpack mode and in `turbopack_mode`, usually
+/// [`NextDynamicMode::Turbopack`].
+///
+/// Most modules don't reference `next/dynamic`, which is checked with a single
+/// read-only traversal, so their output is shared rather than folded by each
+/// backend. The others are folded once, in Turbopack mode, while the
+/// `loadableGenerated` options of Webpack are generated along the way. The
+/// Webpack output is then derived from a copy of the Turbopack one, replacing
+/// these options and removing the imports added for Turbopack, so e.g. the
+/// `Config::event_sink` is notified once. The diagnostics and the stats of
+/// each result are the ones of its backend alone, while the diagnostics are
+/// emitted once.
+///
+/// The outputs differ by more than these in `Config::babel_compat` and
+/// `Config::react_use` modes, or when `turbopack_mode` isn't
+/// [`NextDynamicMode::Turbopack`], in which case the module is folded once per
+/// backend instead.
+///
+/// Like [`transform_batch`](crate::batch::transform_batch), the caller must set
+/// the swc `GLOBALS` (and the `HANDLER`, unless `Config::collect_diagnostics`
+/// is set) on the current thread.
+#[allow(clippy::too_many_arguments)]
+pub fn transform_dual(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    turbopack_mode: NextDynamicMode,
+    filename: FileName,
+    pages_dir: Option<Arc<Path>>,
+    program: Program,
+    config: Arc<Config>,
+) -> DualOutput {
+    let mut scan = SpecifierScan {
+        config: &config,
+        found: false,
+    };
+    program.visit_with(&mut scan);
+    if !scan.found {
+        return DualOutput::Shared(program);
+    }
+
+    let is_single_pass = matches!(turbopack_mode, NextDynamicMode::Turbopack { .. })
+        && !config.babel_compat
+        && !config.react_use;
+    let patcher = |mode| {
+        next_dynamic_patcher_shared(
+            is_development,
+            is_server_compiler,
+            is_react_server_layer,
+            mode,
+            filename.clone(),
+            pages_dir.clone(),
+            config.clone(),
+        )
+    };
+    let transform = |patcher: &mut NextDynamicPatcher, program: Program| {
+        let program = program.fold_with(&mut *patcher);
+        Box::new(TransformResult {
+            filename: filename.clone(),
+            program,
+            diagnostics: std::mem::take(&mut patcher.diagnostics),
+            stats: patcher.stats(),
+            injected_items: patcher.injected_items().clone(),
+        })
+    };
+
+    if !is_single_pass {
+        return DualOutput::Split {
+            webpack: transform(&mut patcher(NextDynamicMode::Webpack), program.clone()),
+            turbopack: transform(&mut patcher(turbopack_mode), program),
+        };
+    }
+
+    let mut turbopack_patcher = patcher(turbopack_mode);
+    turbopack_patcher.webpack_delta = Some(WebpackDelta::new());
+    let mut turbopack = transform(&mut turbopack_patcher, program);
+    let mut webpack_delta = turbopack_patcher
+        .webpack_delta
+        .take()
+        .expect("the delta is recorded by the patcher");
+
+    let mut webpack = turbopack.clone();
+    webpack.program.visit_mut_with(&mut webpack_delta);
+    webpack.injected_items.imports.clear();
+    webpack.stats.items_injected -= webpack_delta.items_removed;
+
+    // Like the patcher, the diagnostics are only collected without a `HANDLER`.
+    let collects_diagnostics = config.collect_diagnostics || !HANDLER.is_set();
+    for diagnostic in webpack_delta.diagnostics {
+        let (is_webpack, is_turbopack) = match diagnostic.code {
+            // Only the Webpack keys are registered in Turbopack mode.
+            DiagnosticCode::KeyCollision | DiagnosticCode::KeyRegistryCollision => (true, false),
+            // Only the Turbopack mode adds imports.
+            DiagnosticCode::ScriptNeedsImports => (false, true),
+            _ => (true, true),
+        };
+        for (result, is_reported) in [(&mut webpack, is_webpack), (&mut turbopack, is_turbopack)] {
+            if is_reported {
+                result.stats.diagnostics_emitted += 1;
+                if collects_diagnostics {
+                    result.diagnostics.push(diagnostic.clone());
+                }
+            }
+        }
+        if !collects_diagnostics {
+            diagnostic.emit();
+        }
+    }
+
+    DualOutput::Split { webpack, turbopack }
+}
+
+/// The differences between the Webpack and the Turbopack outputs of a module,
+/// recorded by the patcher while folding it in Turbopack mode, which turn a
+/// copy of the Turbopack output into the Webpack one.
+#[derive(Debug)]
+pub(crate) struct WebpackDelta {
+    /// The context of the `loadableGenerated` options of the Turbopack output
+    /// replaced by the Webpack ones, which tells them apart from the options
+    /// of the other calls.
+    pub(crate) ctxt: SyntaxContext,
+    /// The `loadableGenerated` options of the Webpack output, in the order
+    /// their `dynamic()` calls were folded. The spans of the calls can't tell
+    /// them apart, e.g. for the calls generated by earlier transforms.
+    pub(crate) loadable_generated: Vec<ObjectLit>,
+    /// The number of `loadable_generated` options already replaced.
+    replaced: usize,
+    /// The locals of the imports added for Turbopack.
+    pub(crate) turbopack_imports: Vec<Id>,
+    /// The number of module items removed along with the Turbopack imports.
+    items_removed: usize,
+    /// The diagnostics of the transform, which are told apart by backend once
+    /// the module is folded.
+    pub(crate) diagnostics: Vec<DynamicDiagnostic>,
+}
+
+impl WebpackDelta {
+    pub(crate) fn new() -> Self {
+        WebpackDelta {
+            ctxt: SyntaxContext::empty().apply_mark(Mark::new()),
+            loadable_generated: vec![],
+            replaced: 0,
+            turbopack_imports: vec![],
+            items_removed: 0,
+            diagnostics: vec![],
+        }
+    }
+
+    fn is_turbopack_import(&self, item: &ModuleItem) -> bool {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                import.specifiers.iter().any(|specifier| {
+                    let local = match specifier {
+                        ImportSpecifier::Named(specifier) => &specifier.local,
+                        ImportSpecifier::Default(specifier) => &specifier.local,
+                        ImportSpecifier::Namespace(specifier) => &specifier.local,
+                    };
+                    self.turbopack_imports.contains(&local.to_id())
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl VisitMut for WebpackDelta {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.visit_mut_children_with(self);
+
+        if self.turbopack_imports.is_empty() {
+            return;
+        }
+        let mut kept = Vec::with_capacity(items.len());
+        for item in items.drain(..) {
+            if self.is_turbopack_import(&item) {
+                // Along with the `"TURBOPACK { ... }"` directive preceding it.
+                kept.pop();
+                self.items_removed += 2;
+            } else {
+                kept.push(item);
+            }
+        }
+        *items = kept;
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        let is_recorded = matches!(
+            loadable_generated_mut(call),
+            Some(Expr::Object(options)) if options.span.ctxt == self.ctxt
+        );
+        if !is_recorded {
+            call.visit_mut_children_with(self);
+            return;
+        }
+
+        // Like the patcher, the loader of a `dynamic()` call, then its callee
+        // and its other arguments, are visited before it, so the options are
+        // replaced in the fold order.
+        if let Some((loader, args)) = call.args.split_first_mut() {
+            loader.visit_mut_with(self);
+            call.callee.visit_mut_with(self);
+            for arg in args {
+                arg.visit_mut_with(self);
+            }
+        }
+        let Some(webpack_generated) = self.loadable_generated.get(self.replaced) else {
+            return;
+        };
+        if let Some(loadable_generated) = loadable_generated_mut(call) {
+            *loadable_generated = Expr::Object(webpack_generated.clone());
+            self.replaced += 1;
+        }
+    }
+}
+
+/// Returns the value of the `loadableGenerated` option of `call`, which the
+/// transform adds to its last options argument.
+fn loadable_generated_mut(call: &mut CallExpr) -> Option<&mut Expr> {
+    let prop = call
+        .args
+        .iter_mut()
+        .rev()
+        .filter_map(|arg| options_object_mut(&mut arg.expr))
+        .flat_map(|options| options.props.iter_mut())
+        .find(|prop| is_key_value_prop(prop, "loadableGenerated"))?;
+    match prop {
+        PropOrSpread::Prop(prop) => match &mut **prop {
+            Prop::KeyValue(prop) => Some(&mut prop.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Looks for the specifiers of `next/dynamic`, which any module importing or
+/// requiring it contains as a string literal.
+struct SpecifierScan<'a> {
+    config: &'a Config,
+    found: bool,
+}
+
+impl Visit for SpecifierScan<'_> {
+    noop_visit_type!();
+
+    fn visit_str(&mut self, str: &Str) {
+        self.found |= self.config.is_dynamic_specifier(&str.value);
+    }
+}
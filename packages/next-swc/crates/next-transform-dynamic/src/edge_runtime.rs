@@ -0,0 +1,243 @@
+//! Reports the Node.js APIs used by modules running in the edge runtime, which
+//! would otherwise only fail once deployed. Must run after the dead branches
+//! are removed, so that the APIs only used by the Node.js runtime, e.g. under
+//! `process.env.NEXT_RUNTIME === 'nodejs'`, aren't reported.
+
+use std::path::{Path, PathBuf};
+
+use swc_core::{
+    common::{FileName, Mark, Span, SyntaxContext},
+    ecma::{
+        ast::{
+            BinExpr, BinaryOp, CallExpr, Callee, CondExpr, Expr, Ident, IfStmt, ImportDecl, Lit,
+            MemberExpr, MemberProp, Module, SwitchStmt, TryStmt, UnaryExpr, UnaryOp,
+        },
+        visit::{
+            as_folder, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitWith,
+        },
+    },
+};
+
+use crate::{messages::Message, rel_filename, DiagnosticLevel, DynamicDiagnostic};
+
+/// Node.js modules which have no equivalent in the edge runtime.
+const NODE_MODULES: &[&str] = &[
+    "child_process",
+    "cluster",
+    "dgram",
+    "dns",
+    "fs",
+    "fs/promises",
+    "http2",
+    "module",
+    "net",
+    "os",
+    "readline",
+    "repl",
+    "tls",
+    "v8",
+    "vm",
+    "worker_threads",
+];
+
+/// `process` methods which aren't available in the edge runtime.
+const PROCESS_METHODS: &[&str] = &["chdir", "cwd", "exit", "memoryUsage", "uptime"];
+
+/// Emits the diagnostics of [`check_edge_runtime`] to the thread-local
+/// `HANDLER`, leaving the module untouched. It must run after the `resolver`
+/// and [`eliminate_dead_branches`](crate::dead_branches::eliminate_dead_branches).
+pub fn edge_runtime_check(
+    filename: FileName,
+    pages_dir: Option<PathBuf>,
+    unresolved_mark: Mark,
+) -> impl Fold {
+    as_folder(EdgeRuntimeCheck {
+        filename,
+        pages_dir,
+        unresolved_mark,
+    })
+}
+
+struct EdgeRuntimeCheck {
+    filename: FileName,
+    pages_dir: Option<PathBuf>,
+    unresolved_mark: Mark,
+}
+
+impl VisitMut for EdgeRuntimeCheck {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let diagnostics = check_edge_runtime(
+            module,
+            &self.filename,
+            self.pages_dir.as_deref(),
+            self.unresolved_mark,
+        );
+        for diagnostic in diagnostics {
+            diagnostic.emit();
+        }
+    }
+}
+
+/// Returns a diagnostic for each Node.js module import, `process` method and
+/// `Buffer` global used by `module`, the module `filename`, whose diagnostics
+/// are relative to `pages_dir`.
+///
+/// The APIs used unconditionally are errors, while the ones guarded by a
+/// condition, e.g. an `if` statement or a `try` block, are warnings, as the
+/// guard may keep them from running in the edge runtime. The operands of
+/// `typeof` aren't reported, as they're used to detect the runtime.
+///
+/// The globals are told apart from the local bindings shadowing them by the
+/// `unresolved_mark` of the `resolver`, which must have run on `module`.
+pub fn check_edge_runtime(
+    module: &Module,
+    filename: &FileName,
+    pages_dir: Option<&Path>,
+    unresolved_mark: Mark,
+) -> Vec<DynamicDiagnostic> {
+    let mut checker = EdgeRuntimeChecker {
+        unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+        conditional_depth: 0,
+        diagnostics: vec![],
+    };
+    module.visit_with(&mut checker);
+
+    let filename = rel_filename(pages_dir, filename);
+    for diagnostic in &mut checker.diagnostics {
+        diagnostic.filename = Some(filename.clone());
+    }
+    checker.diagnostics
+}
+
+struct EdgeRuntimeChecker {
+    /// The context of the identifiers which don't refer to a local binding.
+    unresolved_ctxt: SyntaxContext,
+    /// The number of conditions guarding the visited code.
+    conditional_depth: usize,
+    diagnostics: Vec<DynamicDiagnostic>,
+}
+
+impl EdgeRuntimeChecker {
+    fn is_global(&self, ident: &Ident, name: &str) -> bool {
+        &*ident.sym == name && ident.span.ctxt == self.unresolved_ctxt
+    }
+
+    fn report(&mut self, span: Span, api: &str) {
+        let level = if self.conditional_depth > 0 {
+            DiagnosticLevel::Warning
+        } else {
+            DiagnosticLevel::Error
+        };
+        self.diagnostics.push(DynamicDiagnostic::new(
+            level,
+            span,
+            Message::EdgeRuntimeApi { api },
+        ));
+    }
+
+    fn check_module_specifier(&mut self, span: Span, specifier: &str) {
+        let name = specifier.strip_prefix("node:").unwrap_or(specifier);
+        if NODE_MODULES.contains(&name) {
+            self.report(span, &format!("The Node.js module \"{specifier}\""));
+        }
+    }
+
+    /// Visits `node`, which only runs under a condition.
+    fn visit_conditional<N: VisitWith<Self>>(&mut self, node: &N) {
+        self.conditional_depth += 1;
+        node.visit_with(self);
+        self.conditional_depth -= 1;
+    }
+}
+
+impl Visit for EdgeRuntimeChecker {
+    noop_visit_type!();
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        if !import.type_only {
+            self.check_module_specifier(import.src.span, &import.src.value);
+        }
+    }
+
+    fn visit_if_stmt(&mut self, stmt: &IfStmt) {
+        stmt.test.visit_with(self);
+        self.visit_conditional(&*stmt.cons);
+        if let Some(alt) = &stmt.alt {
+            self.visit_conditional(&**alt);
+        }
+    }
+
+    fn visit_switch_stmt(&mut self, stmt: &SwitchStmt) {
+        stmt.discriminant.visit_with(self);
+        for case in &stmt.cases {
+            self.visit_conditional(case);
+        }
+    }
+
+    fn visit_try_stmt(&mut self, stmt: &TryStmt) {
+        self.visit_conditional(&stmt.block);
+        stmt.handler.visit_with(self);
+        stmt.finalizer.visit_with(self);
+    }
+
+    fn visit_cond_expr(&mut self, expr: &CondExpr) {
+        expr.test.visit_with(self);
+        self.visit_conditional(&*expr.cons);
+        self.visit_conditional(&*expr.alt);
+    }
+
+    fn visit_bin_expr(&mut self, expr: &BinExpr) {
+        expr.left.visit_with(self);
+        match expr.op {
+            BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing => {
+                self.visit_conditional(&*expr.right)
+            }
+            _ => expr.right.visit_with(self),
+        }
+    }
+
+    fn visit_unary_expr(&mut self, expr: &UnaryExpr) {
+        // `typeof Buffer` doesn't use the API, but checks whether it exists.
+        if expr.op != UnaryOp::TypeOf {
+            expr.arg.visit_with(self);
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        call.visit_children_with(self);
+
+        let is_require = match &call.callee {
+            Callee::Expr(callee) => {
+                matches!(&**callee, Expr::Ident(callee) if self.is_global(callee, "require"))
+            }
+            _ => false,
+        };
+        if is_require || matches!(call.callee, Callee::Import(..)) {
+            if let Some(Expr::Lit(Lit::Str(specifier))) = call.args.first().map(|arg| &*arg.expr) {
+                self.check_module_specifier(specifier.span, &specifier.value);
+            }
+        }
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        member.visit_children_with(self);
+
+        if let (Expr::Ident(obj), MemberProp::Ident(prop)) = (&*member.obj, &member.prop) {
+            if self.is_global(obj, "process") && PROCESS_METHODS.contains(&&*prop.sym) {
+                self.report(member.span, &format!("process.{}", prop.sym));
+            }
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        expr.visit_children_with(self);
+
+        if let Expr::Ident(ident) = expr {
+            if self.is_global(ident, "Buffer") {
+                self.report(ident.span, "The Buffer global");
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Compares the options generated for `dynamic()` calls by the Webpack,
+//! Turbopack and Babel compatibility modes, to review behavior changes across
+//! backends.
+
+use std::collections::HashSet;
+
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::{
+        ast::{
+            CallExpr, Callee, EsVersion, Expr, Id, ImportDecl, ImportSpecifier, Module, Pat,
+            VarDeclarator,
+        },
+        codegen::{text_writer::JsWriter, Emitter, Node},
+        parser::{error::Error, parse_file_as_module},
+        visit::{noop_visit_type, FoldWith, Visit, VisitWith},
+    },
+};
+
+use crate::{codemod, interop, next_dynamic_patcher, Config, NextDynamicMode};
+
+/// The options of a `dynamic()` call, as generated by each mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallDiff {
+    /// The index of the call among the `dynamic()` calls of the module.
+    pub index: usize,
+    /// The name of each mode along with the options it generated, printed as
+    /// JavaScript.
+    pub options: Vec<(&'static str, String)>,
+}
+
+impl CallDiff {
+    /// Returns whether the modes generated different options.
+    pub fn differs(&self) -> bool {
+        self.options
+            .windows(2)
+            .any(|options| options[0].1 != options[1].1)
+    }
+}
+
+/// Transforms `source` in each mode and returns the options generated for each
+/// of its `dynamic()` calls, i.e. the calls of `dynamic` imported from the
+/// `dynamic_specifiers`, which default to the ones of [`Config`].
+pub fn golden_diff(
+    source: &str,
+    filename: FileName,
+    is_development: bool,
+    is_server_compiler: bool,
+    dynamic_specifiers: Option<Vec<String>>,
+) -> Result<Vec<CallDiff>, Error> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(filename.clone(), source.into());
+    let module = parse_file_as_module(
+        &fm,
+        codemod::syntax(&filename),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    )?;
+    let config = |babel_compat| Config {
+        collect_diagnostics: true,
+        babel_compat,
+        dynamic_specifiers: dynamic_specifiers.clone(),
+        ..Default::default()
+    };
+    let collector_config = config(false);
+
+    let modes = [
+        ("webpack", NextDynamicMode::Webpack, false),
+        (
+            "turbopack",
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-dynamic".into(),
+                key_export: Default::default(),
+            },
+            false,
+        ),
+        ("babel-compat", NextDynamicMode::Webpack, true),
+    ];
+
+    let outputs = GLOBALS.set(&Default::default(), || {
+        modes
+            .into_iter()
+            .map(|(name, mode, babel_compat)| {
+                let mut patcher = next_dynamic_patcher(
+                    is_development,
+                    is_server_compiler,
+                    false,
+                    mode,
+                    filename.clone(),
+                    None,
+                    config(babel_compat),
+                );
+                let output = module.clone().fold_with(&mut patcher);
+                (name, dynamic_call_options(&cm, &output, &collector_config))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let call_count = outputs
+        .iter()
+        .map(|(_, options)| options.len())
+        .max()
+        .unwrap_or_default();
+
+    Ok((0..call_count)
+        .map(|index| CallDiff {
+            index,
+            options: outputs
+                .iter()
+                .map(|(name, options)| (*name, options.get(index).cloned().unwrap_or_default()))
+                .collect(),
+        })
+        .collect())
+}
+
+/// Returns the printed options of the `dynamic()` calls of `module`, i.e. their
+/// last argument when it's an object literal.
+fn dynamic_call_options(cm: &Lrc<SourceMap>, module: &Module, config: &Config) -> Vec<String> {
+    let mut collector = DynamicCallCollector {
+        config,
+        dynamic_bindings: Default::default(),
+        calls: vec![],
+    };
+    module.visit_with(&mut collector);
+
+    collector
+        .calls
+        .iter()
+        .map(|call| match call.args.last().map(|arg| &*arg.expr) {
+            Some(options @ Expr::Object(..)) => print(cm, options),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+fn print(cm: &Lrc<SourceMap>, expr: &Expr) -> String {
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm.clone(), "\n", &mut buf, None),
+        };
+        expr.emit_with(&mut emitter).unwrap();
+    }
+    String::from_utf8(buf).unwrap()
+}
+
+struct DynamicCallCollector<'a> {
+    config: &'a Config,
+    dynamic_bindings: HashSet<Id>,
+    calls: Vec<CallExpr>,
+}
+
+impl Visit for DynamicCallCollector<'_> {
+    noop_visit_type!();
+
+    fn visit_import_decl(&mut self, decl: &ImportDecl) {
+        if decl.type_only || !self.config.is_dynamic_specifier(&decl.src.value) {
+            return;
+        }
+
+        for specifier in &decl.specifiers {
+            if let ImportSpecifier::Default(specifier) = specifier {
+                self.dynamic_bindings.insert(specifier.local.to_id());
+            }
+        }
+    }
+
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        decl.visit_children_with(self);
+
+        if let (Pat::Ident(name), Some(Expr::Call(init))) = (&decl.name, decl.init.as_deref()) {
+            if interop::require_specifier(init)
+                .is_some_and(|specifier| self.config.is_dynamic_specifier(&specifier.value))
+            {
+                self.dynamic_bindings.insert(name.id.to_id());
+            }
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Ident(callee) = &**callee {
+                if self.dynamic_bindings.contains(&callee.to_id()) {
+                    self.calls.push(call.clone());
+                }
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+}
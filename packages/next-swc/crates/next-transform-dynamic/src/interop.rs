@@ -0,0 +1,67 @@
+//! Recognizes the CommonJS patterns emitted by compilers for ES modules, e.g.
+//! by Babel or TypeScript, so that CommonJS modules can be analyzed like ES
+//! modules.
+
+use swc_core::ecma::ast::{CallExpr, Callee, Expr, Lit, MemberProp, Str};
+
+/// Returns the specifier of `require('specifier')` calls.
+pub fn require_specifier(call: &CallExpr) -> Option<&Str> {
+    if !is_callee_ident(&call.callee, |name| name == "require") || call.args.len() != 1 {
+        return None;
+    }
+
+    match &*call.args[0].expr {
+        Expr::Lit(Lit::Str(specifier)) if call.args[0].spread.is_none() => Some(specifier),
+        _ => None,
+    }
+}
+
+/// Returns the specifier of `_interopRequireDefault(require('specifier'))`
+/// calls, as emitted by Babel and SWC, including their
+/// `_interop_require_default` variant.
+pub fn interop_require_default_specifier(call: &CallExpr) -> Option<&Str> {
+    if !is_callee_ident(&call.callee, |name| {
+        name.ends_with("interopRequireDefault") || name.ends_with("interop_require_default")
+    }) {
+        return None;
+    }
+
+    match call.args.first().map(|arg| &*arg.expr) {
+        Some(Expr::Call(call)) => require_specifier(call),
+        _ => None,
+    }
+}
+
+/// Returns the name of the export defined by
+/// `Object.defineProperty(exports, 'name', descriptor)` calls.
+pub fn define_property_export(call: &CallExpr) -> Option<&Str> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Member(member) = &**callee else {
+        return None;
+    };
+    let (Expr::Ident(object), MemberProp::Ident(method)) = (&*member.obj, &member.prop) else {
+        return None;
+    };
+    if &*object.sym != "Object" || &*method.sym != "defineProperty" || call.args.len() != 3 {
+        return None;
+    }
+
+    match (&*call.args[0].expr, &*call.args[1].expr) {
+        (Expr::Ident(exports), Expr::Lit(Lit::Str(name))) if &*exports.sym == "exports" => {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+fn is_callee_ident(callee: &Callee, predicate: impl Fn(&str) -> bool) -> bool {
+    match callee {
+        Callee::Expr(callee) => match &**callee {
+            Expr::Ident(ident) => predicate(&ident.sym),
+            _ => false,
+        },
+        _ => false,
+    }
+}
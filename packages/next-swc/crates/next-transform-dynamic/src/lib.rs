@@ -1,27 +1,90 @@
 // TODO(alexkirsz) Remove once the diagnostic is fixed.
 #![allow(rustc::untranslatable_diagnostic_trivial)]
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use pathdiff::diff_paths;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
 use swc_core::{
-    common::{errors::HANDLER, FileName, Span, DUMMY_SP},
+    common::{
+        collections::AHashSet,
+        comments::{Comment, CommentKind, Comments},
+        errors::HANDLER,
+        util::take::Take,
+        BytePos, FileName, Span, Spanned, DUMMY_SP,
+    },
     ecma::{
         ast::{
-            ArrayLit, ArrowExpr, BlockStmtOrExpr, Bool, CallExpr, Callee, Expr, ExprOrSpread,
+            ArrayLit, ArrowExpr, BlockStmtOrExpr, Bool, CallExpr, Callee, ComputedPropName, Expr,
             ExprStmt, Id, Ident, ImportDecl, ImportDefaultSpecifier, ImportNamedSpecifier,
-            ImportSpecifier, KeyValueProp, Lit, ModuleDecl, ModuleItem, Null, ObjectLit, Prop,
-            PropName, PropOrSpread, Stmt, Str, Tpl,
+            ImportSpecifier, ImportStarAsSpecifier, KeyValueProp, Lit, MemberExpr, MemberProp,
+            Module, ModuleDecl, ModuleExportName, ModuleItem, Null, ObjectLit, ParenExpr, Pat,
+            Prop, PropName, PropOrSpread, Script, SeqExpr, Stmt, Str, Tpl, TsAsExpr,
+            TsConstAssertion, TsKeywordType, TsKeywordTypeKind, TsNonNullExpr, TsSatisfiesExpr,
+            TsType, TsTypeAnn, TsTypeAssertion, TsTypeParamInstantiation, VarDeclarator,
         },
         utils::{private_ident, ExprFactory},
-        visit::{Fold, FoldWith},
+        visit::{as_folder, noop_visit_mut_type, Fold, FoldWith, VisitMut},
     },
     quote,
 };
 
+/// Logs a decision of the transform with `tracing::debug!`, when the
+/// `debug_log` feature is enabled.
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "debug_log")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+pub mod bare_imports;
+pub mod batch;
+pub mod cache;
+pub mod codegen;
+pub mod codemod;
+pub mod const_eval;
+pub mod dead_branches;
+mod diagnostics;
+pub mod display_names;
+pub mod dual;
+pub mod edge_runtime;
+pub mod golden_diff;
+pub mod interop;
+pub mod lint;
+mod messages;
+mod options;
+mod paths;
+pub mod route_graph;
+pub mod split_report;
+pub mod vite;
+
+use codegen::{
+    hashed_key, loadable_generated_prop, loadable_key, merge_module_id_options, module_id_options,
+    placeholder_options, resolve_module_id, webpack_options, LoadableGenerated,
+};
+pub use diagnostics::{DiagnosticLevel, DynamicDiagnostic, OverlayDiagnostic};
+use dual::WebpackDelta;
+pub use messages::DiagnosticCode;
+use messages::Message;
+
 /// Creates a SWC visitor to transform `next/dynamic` calls to have the
 /// corresponding `loadableGenerated` property.
 ///
+/// The visitor is `Send`, so that modules can be transformed in parallel, e.g.
+/// on a rayon thread pool.
+///
+/// It must run after the styled-jsx transform, which scopes the JSX elements
+/// of the module: the components generated for `dynamic()` calls (e.g. in
+/// `react_use` mode) create elements with `createElement`, so the class names
+/// styled-jsx hashes are only stable when it sees the original module.
+///
 /// [NOTE] We do not use `NextDynamicMode::Turbopack` yet. It isn't compatible
 /// with current loadable manifest, which causes hydration errors.
 pub fn next_dynamic(
@@ -31,29 +94,561 @@ pub fn next_dynamic(
     mode: NextDynamicMode,
     filename: FileName,
     pages_dir: Option<PathBuf>,
-) -> impl Fold {
+    config: Config,
+) -> impl Fold + Send {
+    next_dynamic_patcher(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        mode,
+        filename,
+        pages_dir,
+        config,
+    )
+}
+
+/// Like [`next_dynamic`], but returns the visitor itself, so that the
+/// diagnostics collected with `Config::collect_diagnostics` can be retrieved
+/// with [`NextDynamicPatcher::finish`].
+pub fn next_dynamic_patcher(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    filename: FileName,
+    pages_dir: Option<PathBuf>,
+    config: Config,
+) -> NextDynamicPatcher {
+    next_dynamic_patcher_shared(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        mode,
+        filename,
+        pages_dir.map(Arc::from),
+        Arc::new(config),
+    )
+}
+
+/// Like [`next_dynamic_patcher`], but shares the pages directory and the
+/// config with other patchers, e.g. the ones pooled by the dev server, which
+/// are reused for each module with [`NextDynamicPatcher::reset`].
+pub fn next_dynamic_patcher_shared(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    filename: FileName,
+    pages_dir: Option<Arc<Path>>,
+    config: Arc<Config>,
+) -> NextDynamicPatcher {
+    let layer = TransitionLayer::new(is_server_compiler, is_react_server_layer, config.runtime);
+
     NextDynamicPatcher {
         is_development,
         is_server_compiler,
         is_react_server_layer,
         pages_dir,
         filename,
+        config,
         dynamic_bindings: vec![],
+        dynamic_namespace_bindings: vec![],
+        is_compiled_es_module: false,
         is_next_dynamic_first_arg: false,
+        has_dynamic_import: false,
+        stmt_depth: 0,
         dynamically_imported_specifier: None,
+        generated_keys: Default::default(),
+        react_use_key: None,
+        react_use_idents: None,
+        eager_imports: vec![],
+        is_eager: false,
+        inline_component: None,
+        inline_create_element: None,
+        babel_compat_imports: vec![],
+        diagnostics: vec![],
+        stats: Default::default(),
+        injected_items: Default::default(),
+        magic_comments: vec![],
+        emit_magic_comments: false,
+        declared_bindings: Default::default(),
+        webpack_delta: None,
+        registered_keys: None,
+        defers_diagnostics: false,
         state: match mode {
             NextDynamicMode::Webpack => NextDynamicPatcherState::Webpack,
+            #[cfg(feature = "rspack")]
+            NextDynamicMode::Rspack => NextDynamicPatcherState::Webpack,
             NextDynamicMode::Turbopack {
-                dynamic_transition_name,
+                dynamic_transition_names,
+                key_export,
+            } => NextDynamicPatcherState::Turbopack {
+                dynamic_transition_name: dynamic_transition_names.get(layer).to_string(),
+                key_export,
+                imports: vec![],
+                hybrid: false,
+            },
+            NextDynamicMode::Hybrid {
+                dynamic_transition_names,
+                key_export,
             } => NextDynamicPatcherState::Turbopack {
-                dynamic_transition_name,
+                dynamic_transition_name: dynamic_transition_names.get(layer).to_string(),
+                key_export,
                 imports: vec![],
+                hybrid: true,
+            },
+            NextDynamicMode::Custom(codegen) => NextDynamicPatcherState::Custom {
+                codegen,
+                items: vec![],
             },
         },
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// Like [`next_dynamic`], but returns a visitor usable both as a `VisitMut`
+/// and as a `Fold`, for pipelines composing `VisitMut` passes.
+pub fn next_dynamic_pass(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    filename: FileName,
+    pages_dir: Option<PathBuf>,
+    config: Config,
+) -> impl VisitMut + Fold + Send {
+    as_folder(NextDynamicPass(next_dynamic_patcher(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        mode,
+        filename,
+        pages_dir,
+        config,
+    )))
+}
+
+/// Runs the [`NextDynamicPatcher`] fold in place.
+struct NextDynamicPass(NextDynamicPatcher);
+
+/// Like [`next_dynamic`], but also adds Webpack magic comments to the
+/// `import()` of the loaders:
+/// * `webpackExports` for loaders selecting a named export, e.g. `import(/*
+///   webpackExports: ["Chart"] */ './charts').then((mod) => mod.Chart)`, so
+///   that Webpack can tree-shake the dynamically imported chunk;
+/// * `webpackPrefetch` and `webpackPreload` for calls setting the `prefetch:
+///   true` and `preload: true` options, which are removed from the options
+///   passed to the runtime. Other modes, and [`next_dynamic`], pass them to the
+///   runtime as is.
+#[allow(clippy::too_many_arguments)]
+pub fn next_dynamic_with_comments<C: Comments>(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    mode: NextDynamicMode,
+    filename: FileName,
+    pages_dir: Option<PathBuf>,
+    config: Config,
+    comments: C,
+) -> impl VisitMut + Fold {
+    let mut patcher = next_dynamic_patcher(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        mode,
+        filename,
+        pages_dir,
+        config,
+    );
+    patcher.emit_magic_comments = true;
+    as_folder(NextDynamicCommentsPass { patcher, comments })
+}
+
+/// Runs the [`NextDynamicPatcher`] fold in place, then adds the magic comments
+/// it found to `comments`.
+struct NextDynamicCommentsPass<C: Comments> {
+    patcher: NextDynamicPatcher,
+    comments: C,
+}
+
+impl<C: Comments> VisitMut for NextDynamicCommentsPass<C> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        *module = module.take().fold_with(&mut self.patcher);
+        self.patcher.add_magic_comments(&self.comments);
+    }
+}
+
+impl VisitMut for NextDynamicPass {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        *module = module.take().fold_with(&mut self.0);
+    }
+}
+
+/// Additional options for the `next/dynamic` transform.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Registry shared by all the modules of a compilation, used to detect
+    /// React Loadable Manifest keys generated by more than one module.
+    pub key_registry: Option<Arc<LoadableKeyRegistry>>,
+    /// Collects the React Loadable Manifest entries generated by the module,
+    /// so they can be merged into `react-loadable-manifest.json` as is.
+    pub manifest_entries: Option<Arc<Mutex<Vec<ManifestEntry>>>>,
+    /// Collects the `dynamic()` calls of the module, for the code splitting
+    /// report of [`split_report`].
+    pub dynamic_calls: Option<Arc<Mutex<Vec<DynamicCall>>>>,
+    /// Maps dynamically imported specifiers to the module id emitted in the
+    /// generated code. Specifiers it returns `None` for are emitted as is.
+    pub module_id_resolver: Option<ModuleIdResolver>,
+    /// `jsc.paths` aliases (e.g. `@/*` to `["./src/*"]`) expanded in the
+    /// React Loadable Manifest keys, so they match the paths recorded by the
+    /// manifest plugin.
+    pub paths: Vec<(String, Vec<String>)>,
+    /// The `jsc.baseUrl` the `paths` targets are relative to. When set, the
+    /// expanded targets are made relative to the transformed module, like the
+    /// specifiers resolved by the manifest plugin.
+    pub base_url: Option<PathBuf>,
+    /// The root of the workspace (e.g. of a pnpm or Yarn workspace). Modules
+    /// outside of the pages directory use keys relative to it rather than
+    /// `../..` chains relative to the pages directory.
+    pub root_dir: Option<PathBuf>,
+    /// Names of functions wrapping `dynamic` without changing its behavior,
+    /// e.g. `__wrapped` for `__wrapped(dynamic)(...)` calls added by
+    /// instrumentation.
+    pub transparent_wrappers: Vec<String>,
+    /// The specifiers importing `next/dynamic`, e.g. from code compiled by
+    /// other tools importing its implementation directly. Defaults to
+    /// [`DEFAULT_DYNAMIC_SPECIFIERS`].
+    pub dynamic_specifiers: Option<Vec<String>>,
+    /// The maximum length of React Loadable Manifest keys. Longer keys, e.g.
+    /// of deeply nested modules in monorepos, are replaced with a hash, with
+    /// the original key recorded in their [`ManifestEntry`].
+    pub max_key_length: Option<usize>,
+    /// Replaces the React Loadable Manifest keys of production server builds
+    /// with 8 characters hashes, to shrink the server bundles of pages with
+    /// many `dynamic()` calls. The original keys are recorded in their
+    /// [`ManifestEntry`].
+    ///
+    /// The keys of development builds are kept readable, and production client
+    /// builds don't emit keys, as they reference the modules with
+    /// `require.resolveWeak()` or their Turbopack module ids instead, so the
+    /// option doesn't change their output.
+    pub minify_keys: bool,
+    /// Called with the `loadableGenerated` option of each `dynamic()` call
+    /// before it's inserted, e.g. to add analytics ids or priority hints.
+    pub transform_generated: Option<GeneratedOptionsHook>,
+    /// Environment variables (e.g. `NEXT_PUBLIC_*`) inlined when evaluating
+    /// the `ssr` option, so that expressions like
+    /// `process.env.NEXT_PUBLIC_CSR_ONLY === '1'` are folded to a literal.
+    pub env: HashMap<String, String>,
+    /// [Experimental] Compiles `dynamic()` calls to a component suspending on
+    /// `use(loaderPromiseCache.get(key, loader))` instead of relying on the
+    /// React Loadable runtime.
+    pub react_use: bool,
+    /// Whether the React Compiler runs on the output, in which case the
+    /// components generated in `react_use` mode opt out of memoization with a
+    /// `"use no memo"` directive, as they only forward their props.
+    pub react_compiler: bool,
+    /// Collects diagnostics in the visitor, to be retrieved with
+    /// [`NextDynamicPatcher::finish`], instead of emitting them to the
+    /// thread-local `HANDLER`. Diagnostics are always collected when the
+    /// `HANDLER` isn't set.
+    pub collect_diagnostics: bool,
+    /// Reports warnings as errors, e.g. to fail CI builds on issues that are
+    /// only warned about in development.
+    pub strict: bool,
+    /// The value of the `ssr` option for `dynamic()` calls which don't set
+    /// it, e.g. `Some(false)` for apps rendering every dynamic component on
+    /// the client only.
+    pub default_ssr: Option<bool>,
+    /// The function resolving the module ids of the `webpack` option generated
+    /// for the client in production.
+    pub webpack_resolver: WebpackResolver,
+    /// Whether the module is middleware or an edge API route, which don't
+    /// render React components and thus can't use `next/dynamic`.
+    pub is_edge_entry: bool,
+    /// Whether pages are rendered without streaming SSR, which flushes the
+    /// chunks of dynamically imported modules early. The keys generated in
+    /// Turbopack development mode on the server then only include the module
+    /// id, rather than the chunks too.
+    pub non_streaming: bool,
+    /// The runtime the module is compiled for, which selects the Turbopack
+    /// transition of server modules along with `is_server_compiler` and
+    /// `is_react_server_layer`.
+    pub runtime: DynamicRuntime,
+    /// Specifiers, or `*` patterns like `@/components/hero/*`, of modules to
+    /// import statically, e.g. components found to be above the fold when
+    /// profiling. Their `dynamic()` calls load the statically imported module
+    /// instead of importing it lazily.
+    pub eager_specifiers: Vec<String>,
+    /// Specifiers, or `*` patterns, of modules to import statically in place
+    /// of their `dynamic(() => import('...'))` calls, which are replaced by a
+    /// component rendering the default export synchronously. Unlike with
+    /// `eager_specifiers`, the options of the calls, e.g. `ssr: false`, are
+    /// ignored. Calls with other loaders are left as is.
+    pub inline_specifiers: Vec<String>,
+    /// Replaces `dynamic(() => import('...'))` calls by a component rendering
+    /// the `require()`d module synchronously, so that component tests (e.g.
+    /// run by Jest) render them deterministically, without waiting for the
+    /// loader. Like with `inline_specifiers`, the options are ignored.
+    pub test_stub: bool,
+    /// Resolves the chunks of dynamically imported modules in Webpack
+    /// development mode, so that their keys have the `{ id, chunks }` shape
+    /// of the Turbopack development mode.
+    pub chunk_resolver: Option<ChunkResolver>,
+    /// Whether the module is an AMP-only page, which doesn't load any client
+    /// JavaScript, so that `dynamic()` calls don't need `loadableGenerated`.
+    pub is_amp_only: bool,
+    /// Filename patterns, which may contain a `*` wildcard (e.g.
+    /// `*/node_modules/*`), of modules to leave untouched.
+    pub exclude: Vec<String>,
+    /// Matches the output of the legacy `next/babel` plugin in Webpack mode,
+    /// e.g. to diff compiled output when migrating from Babel: the
+    /// `loadableGenerated` option is added last, keys use the `import()`
+    /// argument as written, and the loader of components not rendered on the
+    /// server is replaced with `async () => null`.
+    pub babel_compat: bool,
+    /// Notified of the rewrites performed by the transform, e.g. for logging
+    /// or build analytics.
+    pub event_sink: Option<Arc<dyn DynamicEventSink>>,
+    /// Keeps the output type-checking when the transform runs before the
+    /// TypeScript types are stripped, e.g. in ts-jest style pipelines: the
+    /// loader of components not rendered on the server is replaced with
+    /// `null!` rather than `null`, and the `props` of the generated components
+    /// are annotated with the props type of the call, or `any`.
+    pub preserve_types: bool,
+}
+
+impl Config {
+    /// Returns whether `specifier` imports `next/dynamic`.
+    pub(crate) fn is_dynamic_specifier(&self, specifier: &str) -> bool {
+        match &self.dynamic_specifiers {
+            Some(dynamic_specifiers) => dynamic_specifiers.iter().any(|s| s == specifier),
+            None => DEFAULT_DYNAMIC_SPECIFIERS.contains(&specifier),
+        }
+    }
+}
+
+/// The specifiers importing `next/dynamic` by default.
+pub const DEFAULT_DYNAMIC_SPECIFIERS: &[&str] = &[
+    "next/dynamic",
+    "next/dynamic.js",
+    "next/dist/shared/lib/dynamic",
+    "next/dist/shared/lib/dynamic.js",
+];
+
+/// The named exports of `next/dynamic`, types included, which can be imported
+/// without a warning. Kept in sync with
+/// `packages/next/src/shared/lib/dynamic.tsx`.
+const NEXT_DYNAMIC_NAMED_EXPORTS: &[&str] = &[
+    "LoaderComponent",
+    "Loader",
+    "LoaderMap",
+    "LoadableGeneratedOptions",
+    "DynamicOptionsLoadingProps",
+    "DynamicOptions",
+    "LoadableOptions",
+    "LoadableFn",
+    "LoadableComponent",
+    "noSSR",
+];
+
+/// Resolves a specifier imported from the given module to a module id, e.g.
+/// by applying `tsconfig` paths, extensions and index files.
+pub struct ModuleIdResolver(Box<dyn Fn(&str, &FileName) -> Option<String> + Send + Sync>);
+
+impl ModuleIdResolver {
+    pub fn new(
+        resolve: impl Fn(&str, &FileName) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        ModuleIdResolver(Box::new(resolve))
+    }
+
+    fn resolve(&self, specifier: &str, filename: &FileName) -> Option<String> {
+        (self.0)(specifier, filename)
+    }
+}
+
+impl std::fmt::Debug for ModuleIdResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ModuleIdResolver").finish()
+    }
+}
+
+/// Transforms the generated `loadableGenerated` option of a `dynamic()` call.
+pub struct GeneratedOptionsHook(Box<dyn Fn(&mut ObjectLit, &DynamicCallContext) + Send + Sync>);
+
+impl GeneratedOptionsHook {
+    pub fn new(
+        transform: impl Fn(&mut ObjectLit, &DynamicCallContext) + Send + Sync + 'static,
+    ) -> Self {
+        GeneratedOptionsHook(Box::new(transform))
+    }
+
+    fn transform(&self, options: &mut ObjectLit, call: &DynamicCallContext) {
+        (self.0)(options, call)
+    }
+}
+
+impl std::fmt::Debug for GeneratedOptionsHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("GeneratedOptionsHook").finish()
+    }
+}
+
+/// Resolves a module id to the chunks needed to load it, if known.
+pub struct ChunkResolver(Box<dyn Fn(&str) -> Option<Vec<String>> + Send + Sync>);
+
+impl ChunkResolver {
+    pub fn new(resolve: impl Fn(&str) -> Option<Vec<String>> + Send + Sync + 'static) -> Self {
+        ChunkResolver(Box::new(resolve))
+    }
+
+    fn resolve(&self, module_id: &str) -> Option<Vec<String>> {
+        (self.0)(module_id)
+    }
+}
+
+impl std::fmt::Debug for ChunkResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ChunkResolver").finish()
+    }
+}
+
+/// A React Loadable Manifest entry generated for a `dynamic()` call.
+///
+/// Only generated in Webpack mode, as Turbopack adds manifest entries through
+/// the dynamic transition instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// The key of the entry, as found in `loadableGenerated.modules`.
+    pub key: String,
+    /// The dynamically imported module specifier.
+    pub specifier: String,
+    /// The module containing the `dynamic()` call.
+    pub file: String,
+    /// The directory the path of the module in `key` is relative to.
+    #[serde(default)]
+    pub key_base: KeyBase,
+    /// The key the hashed `key` was generated from, when it was hashed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_key: Option<String>,
+}
+
+/// A `dynamic()` call importing a static specifier, as collected by
+/// `Config::dynamic_calls`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicCall {
+    /// The dynamically imported module specifier.
+    pub specifier: String,
+    /// The module containing the `dynamic()` call.
+    pub file: String,
+    /// Whether the component is rendered on the server, i.e. the call doesn't
+    /// set `ssr: false`.
+    pub ssr: bool,
+}
+
+/// The `require` function resolving module ids in the `webpack` option.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebpackResolver {
+    /// `require.resolveWeak`, which doesn't add the module to the chunk.
+    #[default]
+    ResolveWeak,
+    /// `require.resolve`, for setups which can't resolve weak dependencies,
+    /// e.g. some custom servers and Webpack externals configurations.
+    Resolve,
+}
+
+impl WebpackResolver {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebpackResolver::ResolveWeak => "resolveWeak",
+            WebpackResolver::Resolve => "resolve",
+        }
+    }
+}
+
+/// The directory the path of a module is relative to in the React Loadable
+/// Manifest keys.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyBase {
+    /// The pages directory, or the path is absolute when there is none.
+    #[default]
+    PagesDir,
+    /// `Config::root_dir`, for modules outside of the pages directory, e.g. in
+    /// the shared packages of a workspace.
+    RootDir,
+}
+
+/// Records which module generated each React Loadable Manifest key.
+///
+/// Two distinct modules generating the same key (e.g. symlinked files, or
+/// files only differing in casing on a case-insensitive filesystem) cause the
+/// runtime to load the wrong chunk for one of them.
+#[derive(Debug, Default)]
+pub struct LoadableKeyRegistry {
+    /// Maps lowercased keys to the key and the module that generated it.
+    keys: Mutex<HashMap<String, (String, String)>>,
+}
+
+impl LoadableKeyRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers `key`, generated by a `dynamic()` call of `filename` importing
+    /// `specifier`. Returns an error when another module registered a
+    /// colliding key.
+    pub(crate) fn check(
+        &self,
+        key: &str,
+        filename: &FileName,
+        specifier: &str,
+        span: Span,
+    ) -> Option<DynamicDiagnostic> {
+        let (existing_key, existing_filename) = self.register(key, &filename.to_string())?;
+        let mut diagnostic = DynamicDiagnostic::new(
+            DiagnosticLevel::Error,
+            span,
+            Message::KeyRegistryCollision {
+                key,
+                existing_key: &existing_key,
+                existing_filename: &existing_filename,
+            },
+        );
+        diagnostic.specifier = Some(specifier.to_string());
+        Some(diagnostic)
+    }
+
+    /// Registers `key` as generated by `filename`. Returns the key and the
+    /// module that previously registered a colliding key, if any.
+    fn register(&self, key: &str, filename: &str) -> Option<(String, String)> {
+        let mut keys = self.keys.lock().unwrap();
+        match keys.entry(key.to_lowercase()) {
+            Entry::Occupied(entry) => {
+                let (existing_key, existing_filename) = entry.get();
+                (existing_filename != filename)
+                    .then(|| (existing_key.clone(), existing_filename.clone()))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((key.to_string(), filename.to_string()));
+                None
+            }
+        }
+    }
+}
+
+/// New backends may be added in minor releases, so matches on the mode should
+/// have a wildcard arm, or use the accessors like
+/// [`NextDynamicMode::dynamic_transition_names`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum NextDynamicMode {
     /// In Webpack mode, each `dynamic()` call will generate a key composed
     /// from:
@@ -72,390 +667,2340 @@ pub enum NextDynamicMode {
     ///   need for a manifest entry
     /// * during build, each `dynamic()` call will import the module through the
     ///   given transition, which takes care of adding an entry to the manifest
-    ///   and returning an asset that exports the entry's key.
-    Turbopack { dynamic_transition_name: String },
+    ///   and returning an asset that exports the entry's key, as described by
+    ///   `key_export`.
+    ///
+    /// The transition is chosen per layer, e.g. edge server modules may use a
+    /// different transition than Node.js ones.
+    Turbopack {
+        dynamic_transition_names: TransitionNames,
+        key_export: TurbopackKeyExport,
+    },
+    /// In Custom mode, the given codegen generates the `loadableGenerated`
+    /// option of each `dynamic()` call, along with the module items it needs.
+    Custom(Arc<dyn DynamicCodegen>),
+    /// In Hybrid mode, each `dynamic()` call generates the keys of both the
+    /// Webpack and the Turbopack modes, so that the same output can be
+    /// bundled by either while migrating from one to the other. The Turbopack
+    /// module id is appended to the `modules` generated by the Webpack mode.
+    Hybrid {
+        dynamic_transition_names: TransitionNames,
+        key_export: TurbopackKeyExport,
+    },
+    /// [Experimental] In Rspack mode, each `dynamic()` call generates the keys
+    /// of the Webpack mode, which Rspack's React Loadable plugin is compatible
+    /// with.
+    #[cfg(feature = "rspack")]
+    Rspack,
+}
+
+impl NextDynamicMode {
+    /// Returns the names of the transitions used to import dynamically
+    /// imported modules, in the Turbopack and Hybrid modes.
+    pub fn dynamic_transition_names(&self) -> Option<&TransitionNames> {
+        match self {
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names,
+                ..
+            }
+            | NextDynamicMode::Hybrid {
+                dynamic_transition_names,
+                ..
+            } => Some(dynamic_transition_names),
+            _ => None,
+        }
+    }
+
+    /// Returns how the Turbopack transition exports keys, in the Turbopack and
+    /// Hybrid modes.
+    pub fn key_export(&self) -> Option<&TurbopackKeyExport> {
+        match self {
+            NextDynamicMode::Turbopack { key_export, .. }
+            | NextDynamicMode::Hybrid { key_export, .. } => Some(key_export),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the mode generates the React Loadable Manifest keys of
+    /// the Webpack mode.
+    pub fn has_webpack_keys(&self) -> bool {
+        match self {
+            NextDynamicMode::Webpack | NextDynamicMode::Hybrid { .. } => true,
+            #[cfg(feature = "rspack")]
+            NextDynamicMode::Rspack => true,
+            NextDynamicMode::Turbopack { .. } | NextDynamicMode::Custom(..) => false,
+        }
+    }
+}
+
+impl PartialEq for NextDynamicMode {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (NextDynamicMode::Webpack, NextDynamicMode::Webpack) => true,
+            #[cfg(feature = "rspack")]
+            (NextDynamicMode::Rspack, NextDynamicMode::Rspack) => true,
+            (
+                NextDynamicMode::Turbopack {
+                    dynamic_transition_names,
+                    key_export,
+                },
+                NextDynamicMode::Turbopack {
+                    dynamic_transition_names: other_dynamic_transition_names,
+                    key_export: other_key_export,
+                },
+            ) => {
+                dynamic_transition_names == other_dynamic_transition_names
+                    && key_export == other_key_export
+            }
+            (
+                NextDynamicMode::Hybrid {
+                    dynamic_transition_names,
+                    key_export,
+                },
+                NextDynamicMode::Hybrid {
+                    dynamic_transition_names: other_dynamic_transition_names,
+                    key_export: other_key_export,
+                },
+            ) => {
+                dynamic_transition_names == other_dynamic_transition_names
+                    && key_export == other_key_export
+            }
+            (NextDynamicMode::Custom(codegen), NextDynamicMode::Custom(other_codegen)) => {
+                Arc::ptr_eq(codegen, other_codegen)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for NextDynamicMode {}
+
+/// Formats the mode as `webpack`, `turbopack:<transitions>` or
+/// `hybrid:<transitions>`, followed by `:<key export>` when the key export
+/// isn't the default one. The transitions are formatted as described by
+/// [`TransitionNames`]. Custom modes are formatted as `custom`, which can't be
+/// parsed back.
+impl fmt::Display for NextDynamicMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, dynamic_transition_names, key_export) = match self {
+            NextDynamicMode::Webpack => return f.write_str("webpack"),
+            #[cfg(feature = "rspack")]
+            NextDynamicMode::Rspack => return f.write_str("rspack"),
+            NextDynamicMode::Custom(..) => return f.write_str("custom"),
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names,
+                key_export,
+            } => ("turbopack", dynamic_transition_names, key_export),
+            NextDynamicMode::Hybrid {
+                dynamic_transition_names,
+                key_export,
+            } => ("hybrid", dynamic_transition_names, key_export),
+        };
+
+        write!(f, "{name}:{dynamic_transition_names}")?;
+        if *key_export != TurbopackKeyExport::default() {
+            write!(f, ":{key_export}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NextDynamicMode {
+    type Err = ParseModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ':');
+        let name = parts.next().unwrap_or_default();
+        if name == "webpack" && s == name {
+            return Ok(NextDynamicMode::Webpack);
+        }
+        #[cfg(feature = "rspack")]
+        if name == "rspack" && s == name {
+            return Ok(NextDynamicMode::Rspack);
+        }
+
+        let dynamic_transition_names = match parts.next() {
+            Some(dynamic_transition_names) => dynamic_transition_names
+                .parse()
+                .map_err(|_| ParseModeError(s.to_string()))?,
+            None => return Err(ParseModeError(s.to_string())),
+        };
+        let key_export = match parts.next() {
+            Some(key_export) => key_export.parse()?,
+            None => Default::default(),
+        };
+
+        match name {
+            "turbopack" => Ok(NextDynamicMode::Turbopack {
+                dynamic_transition_names,
+                key_export,
+            }),
+            "hybrid" => Ok(NextDynamicMode::Hybrid {
+                dynamic_transition_names,
+                key_export,
+            }),
+            _ => Err(ParseModeError(s.to_string())),
+        }
+    }
+}
+
+impl Serialize for NextDynamicMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let NextDynamicMode::Custom(..) = self {
+            return Err(ser::Error::custom(
+                "custom next/dynamic modes can't be serialized",
+            ));
+        }
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NextDynamicMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The error returned when parsing an invalid [`NextDynamicMode`] or
+/// [`TurbopackKeyExport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseModeError(String);
+
+impl fmt::Display for ParseModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid next/dynamic mode \"{}\", expected \"webpack\", ",
+            self.0
+        )?;
+        #[cfg(feature = "rspack")]
+        f.write_str("\"rspack\", ")?;
+        f.write_str(
+            "\"turbopack:<transitions>[:<key export>]\" or \"hybrid:<transitions>[:<key export>]\"",
+        )
+    }
+}
+
+impl std::error::Error for ParseModeError {}
+
+/// Generates the code of `dynamic()` calls for a custom loadable runtime, for
+/// frameworks built on top of next-swc.
+pub trait DynamicCodegen: std::fmt::Debug + Send + Sync {
+    fn generate(&self, call: &DynamicCallContext) -> DynamicCodegenOutput;
+
+    /// Identifies the code generated by the codegen, e.g. its name along with
+    /// its options, for the [`cache_key`](cache::cache_key) of the modules
+    /// using it. Modules aren't cached when it returns `None`, the default.
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Receives the decisions made by the transform. All methods do nothing by
+/// default.
+pub trait DynamicEventSink: std::fmt::Debug + Send + Sync {
+    /// Called for each `dynamic()` call importing a static specifier.
+    fn on_call_detected(&self, _filename: &FileName, _specifier: &str, _span: Span) {}
+
+    /// Called for each React Loadable Manifest key generated.
+    fn on_key_generated(&self, _filename: &FileName, _key: &str, _span: Span) {}
+
+    /// Called when the loader of a `dynamic()` call is removed, because the
+    /// component isn't rendered on the server.
+    fn on_loader_stripped(&self, _filename: &FileName, _span: Span) {}
 }
 
+/// Describes the `dynamic()` call to generate code for.
 #[derive(Debug)]
-struct NextDynamicPatcher {
-    is_development: bool,
-    is_server_compiler: bool,
-    is_react_server_layer: bool,
-    pages_dir: Option<PathBuf>,
-    filename: FileName,
-    dynamic_bindings: Vec<Id>,
-    is_next_dynamic_first_arg: bool,
-    dynamically_imported_specifier: Option<(String, Span)>,
-    state: NextDynamicPatcherState,
+pub struct DynamicCallContext<'a> {
+    /// The dynamically imported module id.
+    pub module_id: &'a str,
+    /// The span of the dynamically imported specifier.
+    pub span: Span,
+    /// The module containing the `dynamic()` call.
+    pub filename: &'a FileName,
+    pub pages_dir: Option<&'a Path>,
+    pub is_development: bool,
+    pub is_server_compiler: bool,
+    pub is_react_server_layer: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct DynamicCodegenOutput {
+    /// The props of the `loadableGenerated` option.
+    pub props: Vec<PropOrSpread>,
+    /// Module items to add to the module, e.g. imports of the runtime. They're
+    /// inserted after the directives and the imports of the module.
+    pub module_items: Vec<ModuleItem>,
 }
 
+/// How the asset returned by the Turbopack build transition exports the key of
+/// its React Loadable Manifest entry.
 #[derive(Debug, Clone, Eq, PartialEq)]
-enum NextDynamicPatcherState {
-    Webpack,
-    /// In Turbo mode, contains a list of modules that need to be imported with
-    /// the given transition under a particular ident.
-    #[allow(unused)]
-    Turbopack {
-        dynamic_transition_name: String,
-        imports: Vec<TurbopackImport>,
-    },
+#[non_exhaustive]
+pub enum TurbopackKeyExport {
+    Default,
+    Named(String),
+}
+
+impl Default for TurbopackKeyExport {
+    fn default() -> Self {
+        TurbopackKeyExport::Named("__turbopack_module_id__".into())
+    }
+}
+
+/// Formats the key export as `default`, or as the name of the export.
+impl fmt::Display for TurbopackKeyExport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TurbopackKeyExport::Default => f.write_str("default"),
+            TurbopackKeyExport::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+impl FromStr for TurbopackKeyExport {
+    type Err = ParseModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseModeError(s.to_string())),
+            "default" => Ok(TurbopackKeyExport::Default),
+            name => Ok(TurbopackKeyExport::Named(name.to_string())),
+        }
+    }
+}
+
+impl Serialize for TurbopackKeyExport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TurbopackKeyExport {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The runtime a module is compiled for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum DynamicRuntime {
+    #[default]
+    Nodejs,
+    Edge,
+}
+
+/// The layer a module is compiled for, which selects the Turbopack transition
+/// in [`TransitionNames`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum TransitionLayer {
+    Client,
+    Server,
+    EdgeServer,
+    ReactServer,
+    EdgeReactServer,
+}
+
+impl TransitionLayer {
+    /// Returns the layer of a module from the options of the transform.
+    pub fn new(
+        is_server_compiler: bool,
+        is_react_server_layer: bool,
+        runtime: DynamicRuntime,
+    ) -> Self {
+        match (is_server_compiler, is_react_server_layer, runtime) {
+            (false, ..) => TransitionLayer::Client,
+            (true, false, DynamicRuntime::Nodejs) => TransitionLayer::Server,
+            (true, false, DynamicRuntime::Edge) => TransitionLayer::EdgeServer,
+            (true, true, DynamicRuntime::Nodejs) => TransitionLayer::ReactServer,
+            (true, true, DynamicRuntime::Edge) => TransitionLayer::EdgeReactServer,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            TransitionLayer::Client => "client",
+            TransitionLayer::Server => "server",
+            TransitionLayer::EdgeServer => "edge-server",
+            TransitionLayer::ReactServer => "react-server",
+            TransitionLayer::EdgeReactServer => "edge-react-server",
+        }
+    }
+}
+
+impl FromStr for TransitionLayer {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            TransitionLayer::Client,
+            TransitionLayer::Server,
+            TransitionLayer::EdgeServer,
+            TransitionLayer::ReactServer,
+            TransitionLayer::EdgeReactServer,
+        ]
+        .into_iter()
+        .find(|layer| layer.as_str() == s)
+        .ok_or(())
+    }
+}
+
+/// The names of the Turbopack transitions used to import dynamically imported
+/// modules, per [`TransitionLayer`]. Layers without a transition of their own
+/// use the default one.
+///
+/// Formatted as the default transition followed by the overrides, e.g.
+/// `next-dynamic,edge-server=next-dynamic-edge`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransitionNames {
+    pub default: String,
+    pub layers: BTreeMap<TransitionLayer, String>,
+}
+
+impl TransitionNames {
+    pub fn new(default: impl Into<String>) -> Self {
+        TransitionNames {
+            default: default.into(),
+            layers: Default::default(),
+        }
+    }
+
+    /// Uses `name` as the transition of `layer`.
+    pub fn with_layer(mut self, layer: TransitionLayer, name: impl Into<String>) -> Self {
+        self.layers.insert(layer, name.into());
+        self
+    }
+
+    /// Returns the transition of `layer`.
+    pub fn get(&self, layer: TransitionLayer) -> &str {
+        self.layers.get(&layer).unwrap_or(&self.default)
+    }
+}
+
+impl From<&str> for TransitionNames {
+    fn from(default: &str) -> Self {
+        TransitionNames::new(default)
+    }
+}
+
+impl From<String> for TransitionNames {
+    fn from(default: String) -> Self {
+        TransitionNames::new(default)
+    }
+}
+
+impl fmt::Display for TransitionNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.default)?;
+        for (layer, name) in &self.layers {
+            write!(f, ",{}={name}", layer.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TransitionNames {
+    type Err = ParseModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseModeError(s.to_string());
+        let mut parts = s.split(',');
+        let mut names = match parts.next() {
+            Some(default) if !default.is_empty() && !default.contains('=') => {
+                TransitionNames::new(default)
+            }
+            _ => return Err(err()),
+        };
+        for part in parts {
+            let (layer, name) = part.split_once('=').ok_or_else(err)?;
+            if name.is_empty() {
+                return Err(err());
+            }
+            names = names.with_layer(layer.parse().map_err(|_| err())?, name);
+        }
+        Ok(names)
+    }
+}
+
+#[derive(Debug)]
+pub struct NextDynamicPatcher {
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    pages_dir: Option<Arc<Path>>,
+    filename: FileName,
+    config: Arc<Config>,
+    dynamic_bindings: Vec<Id>,
+    /// Bindings of objects whose `default` property is `dynamic`, as
+    /// compiled from ES modules to CommonJS.
+    dynamic_namespace_bindings: Vec<Id>,
+    /// Whether the module was compiled from an ES module to CommonJS, as
+    /// marked by `Object.defineProperty(exports, '__esModule', ...)`, so that
+    /// its `require()` calls return the namespaces of ES modules.
+    is_compiled_es_module: bool,
+    is_next_dynamic_first_arg: bool,
+    /// Whether an `import()` expression was found in the first argument of the
+    /// current `dynamic()` call.
+    has_dynamic_import: bool,
+    /// The nesting depth of the statement being folded, 1 for the statements
+    /// at the top level of the module.
+    stmt_depth: usize,
+    dynamically_imported_specifier: Option<(String, Span)>,
+    /// Maps the lowercased keys generated in this module to the key and the
+    /// module id they were generated for.
+    generated_keys: HashMap<String, (String, String)>,
+    /// In `react_use` mode, the key of the `dynamic()` call that was just
+    /// transformed, to be turned into a component by `fold_expr`.
+    react_use_key: Option<Expr>,
+    /// In `react_use` mode, the idents of the runtime helpers to import.
+    react_use_idents: Option<ReactUseIdents>,
+    /// The specifiers of `Config::eager_specifiers` imported by this module,
+    /// along with the ident of their namespace import.
+    eager_imports: Vec<(String, Ident)>,
+    /// Whether the loader of the current `dynamic()` call imports an eager
+    /// specifier.
+    is_eager: bool,
+    /// The component replacing the `dynamic()` call that was just
+    /// transformed, for `Config::inline_specifiers` and `Config::test_stub`,
+    /// to be swapped in by `fold_expr`.
+    inline_component: Option<Expr>,
+    /// The ident of `createElement`, imported for the components of
+    /// `Config::inline_specifiers` and `Config::test_stub`.
+    inline_create_element: Option<Ident>,
+    /// In `babel_compat` mode, the arguments of the `import()` expressions of
+    /// the loader of the current `dynamic()` call.
+    babel_compat_imports: Vec<Box<Expr>>,
+    /// Diagnostics collected when `Config::collect_diagnostics` is set.
+    diagnostics: Vec<DynamicDiagnostic>,
+    stats: TransformStats,
+    state: NextDynamicPatcherState,
+    injected_items: InjectedItems,
+    /// The Webpack magic comments of the `dynamic()` calls (e.g.
+    /// `webpackExports: ["Chart"]`), along with the position of the
+    /// dynamically imported specifier, where [`next_dynamic_with_comments`]
+    /// adds them.
+    magic_comments: Vec<(BytePos, String)>,
+    /// Whether the `magic_comments` are added to the module, i.e. when run by
+    /// [`next_dynamic_with_comments`].
+    emit_magic_comments: bool,
+    /// The bindings declared in the module, which may shadow the globals of
+    /// the `ssr` options evaluated at compile time.
+    declared_bindings: AHashSet<Id>,
+    /// When run by [`transform_dual`](dual::transform_dual) in Turbopack
+    /// mode, the differences of the Webpack output, recorded along the way.
+    webpack_delta: Option<WebpackDelta>,
+    /// When run by [`transform_cached`](cache::transform_cached), the keys
+    /// generated by the module along with their specifier and span, to be
+    /// registered in the `Config::key_registry` again when the output is
+    /// reused.
+    registered_keys: Option<Vec<(String, String, Span)>>,
+    /// Whether the diagnostics are collected even when the `HANDLER` is set,
+    /// i.e. when run by [`transform_batch`](batch::transform_batch), which
+    /// emits them in the order of the modules.
+    defers_diagnostics: bool,
+}
+
+/// The module items added by the transform, e.g. for the chunking and
+/// manifest bookkeeping of Turbopack.
+///
+/// The items are inserted as a single block after the directives and the
+/// imports of the module: first the eager imports, then the helper imports,
+/// then the Turbopack imports, each in the order of the `dynamic()` calls.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectedItems {
+    /// The Turbopack imports of dynamically imported modules, each preceded
+    /// by its `"TURBOPACK { ... }"` directive.
+    ///
+    /// The imports are in the source order of the first `dynamic()` call
+    /// importing each module, so the output only depends on the source of the
+    /// module, e.g. for reproducible builds and remote caching. Calls generated
+    /// by earlier transforms, without spans, come first, in the order they're
+    /// folded.
+    pub imports: Vec<InjectedImport>,
+    /// The modules imported statically for `Config::eager_specifiers` and
+    /// `Config::inline_specifiers`.
+    pub eager_imports: Vec<String>,
+    /// The runtime helper modules imported, e.g. `react` in `react_use` mode,
+    /// or the ones imported by the items of a [`DynamicCodegen`].
+    pub helper_imports: Vec<String>,
+}
+
+/// A Turbopack import of a dynamically imported module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectedImport {
+    pub specifier: String,
+    /// The transition the module is imported through, or `None` when it's only
+    /// imported for its id, with the `chunking-type: none` directive.
+    pub transition: Option<String>,
+}
+
+/// Statistics about the transform of a module, e.g. for compiler metrics.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TransformStats {
+    /// The number of `dynamic()` calls transformed.
+    pub calls_transformed: usize,
+    /// The number of module items added to the module, e.g. imports.
+    pub items_injected: usize,
+    /// The number of diagnostics reported, including the ones emitted to the
+    /// `HANDLER`.
+    pub diagnostics_emitted: usize,
+    /// The time spent transforming the module.
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct ReactUseIdents {
+    react_use: Ident,
+    create_element: Ident,
+    loader_promise_cache: Ident,
+}
+
+/// The module exporting the promise cache used in `react_use` mode.
+const LOADER_PROMISE_CACHE_MODULE: &str = "next/dist/shared/lib/loader-promise-cache";
+
+#[derive(Debug, Clone)]
+enum NextDynamicPatcherState {
+    Webpack,
+    /// In Custom mode, contains the module items to add to the module, as
+    /// returned by the codegen.
+    Custom {
+        codegen: Arc<dyn DynamicCodegen>,
+        items: Vec<ModuleItem>,
+    },
+    /// In Turbo mode, contains a list of modules that need to be imported with
+    /// the given transition under a particular ident. In Hybrid mode, the
+    /// Webpack keys are generated as well.
+    #[allow(unused)]
+    Turbopack {
+        dynamic_transition_name: String,
+        key_export: TurbopackKeyExport,
+        imports: Vec<TurbopackImport>,
+        hybrid: bool,
+    },
+}
+
+#[cfg(feature = "debug_log")]
+impl NextDynamicPatcherState {
+    fn name(&self) -> &'static str {
+        match self {
+            NextDynamicPatcherState::Webpack => "webpack",
+            NextDynamicPatcherState::Custom { .. } => "custom",
+            NextDynamicPatcherState::Turbopack { hybrid: false, .. } => "turbopack",
+            NextDynamicPatcherState::Turbopack { hybrid: true, .. } => "hybrid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TurbopackImport {
+    /// `chunks_ident` is only set for streaming renders, which flush the
+    /// chunks of the dynamically imported modules early.
+    DevelopmentTransition {
+        id_ident: Ident,
+        chunks_ident: Option<Ident>,
+        specifier: String,
+    },
+    DevelopmentId {
+        id_ident: Ident,
+        specifier: String,
+    },
+    BuildTransition {
+        id_ident: Ident,
+        specifier: String,
+    },
+    BuildId {
+        id_ident: Ident,
+        specifier: String,
+    },
+}
+
+impl TurbopackImport {
+    fn new(
+        is_development: bool,
+        is_server_compiler: bool,
+        streaming: bool,
+        specifier: String,
+        specifier_span: Span,
+    ) -> Self {
+        let id_ident = private_ident!(specifier_span, "id");
+
+        match (is_development, is_server_compiler) {
+            (true, true) => TurbopackImport::DevelopmentTransition {
+                id_ident,
+                chunks_ident: streaming.then(|| private_ident!(specifier_span, "chunks")),
+                specifier,
+            },
+            (true, false) => TurbopackImport::DevelopmentId {
+                id_ident,
+                specifier,
+            },
+            (false, true) => TurbopackImport::BuildTransition {
+                id_ident,
+                specifier,
+            },
+            (false, false) => TurbopackImport::BuildId {
+                id_ident,
+                specifier,
+            },
+        }
+    }
+
+    /// The span of the `import()` specifier of the first call importing the
+    /// module, which the imports are ordered by.
+    fn span(&self) -> Span {
+        self.id_ident().span
+    }
+
+    fn id_ident(&self) -> &Ident {
+        match self {
+            TurbopackImport::DevelopmentTransition { id_ident, .. }
+            | TurbopackImport::DevelopmentId { id_ident, .. }
+            | TurbopackImport::BuildTransition { id_ident, .. }
+            | TurbopackImport::BuildId { id_ident, .. } => id_ident,
+        }
+    }
+
+    fn specifier(&self) -> &str {
+        match self {
+            TurbopackImport::DevelopmentTransition { specifier, .. }
+            | TurbopackImport::DevelopmentId { specifier, .. }
+            | TurbopackImport::BuildTransition { specifier, .. }
+            | TurbopackImport::BuildId { specifier, .. } => specifier,
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl NextDynamicPatcher {
+    /// Returns the bindings of `next/dynamic` found so far.
+    pub fn dynamic_bindings(&self) -> &[Id] {
+        &self.dynamic_bindings
+    }
+
+    /// Returns the specifiers imported through the Turbopack transition so
+    /// far, in the order of [`InjectedItems::imports`], which are added to the
+    /// module once it's folded.
+    pub fn turbopack_imports(&self) -> Vec<&str> {
+        match &self.state {
+            NextDynamicPatcherState::Turbopack { imports, .. } => {
+                imports.iter().map(|import| import.specifier()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns the diagnostics collected with `Config::collect_diagnostics`
+    /// so far.
+    pub fn pending_diagnostics(&self) -> &[DynamicDiagnostic] {
+        &self.diagnostics
+    }
+}
+
+impl Fold for NextDynamicPatcher {
+    fn fold_module(&mut self, module: Module) -> Module {
+        if self.is_excluded() {
+            return module;
+        }
+
+        self.declared_bindings = const_eval::declared_bindings(&module);
+        module.fold_children_with(self)
+    }
+
+    fn fold_module_items(&mut self, mut items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("next_dynamic::fold_module", filename = %self.filename).entered();
+
+        let start = now();
+
+        items = items.fold_children_with(self);
+
+        // The injected items are inserted as a single block after the
+        // directives, like `"use client"`, and the imports of the module, so
+        // their position doesn't depend on which of them are needed.
+        let mut injected = vec![];
+        self.maybe_add_eager_imports(&mut injected);
+        self.maybe_add_inline_imports(&mut injected);
+        self.maybe_add_react_use_imports(&mut injected);
+        self.maybe_add_custom_items(&mut injected);
+        self.maybe_add_dynamically_imported_specifier(&mut injected);
+
+        self.stats.items_injected += injected.len();
+        debug_log!(
+            filename = %self.filename,
+            items = injected.len(),
+            "next_dynamic::items_injected"
+        );
+        if !injected.is_empty() {
+            let index = injection_index(&items);
+            // Shifts the existing items in place, rather than moving them to a
+            // new vector.
+            items.splice(index..index, injected);
+        }
+        if let Some(start) = start {
+            self.stats.duration += start.elapsed();
+        }
+
+        items
+    }
+
+    fn fold_script(&mut self, script: Script) -> Script {
+        if self.is_excluded() {
+            return script;
+        }
+
+        self.declared_bindings = const_eval::declared_bindings(&script);
+        let script = script.fold_children_with(self);
+
+        // Only the Webpack mode can transform `dynamic()` calls without adding
+        // imports, which scripts can't contain.
+        if self.has_module_items_to_add() {
+            self.emit(
+                DiagnosticLevel::Error,
+                script.span,
+                Message::ScriptNeedsImports,
+            );
+        }
+
+        script
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children_with(self);
+
+        if let Expr::Call(..) = expr {
+            if let Some(component) = self.inline_component.take() {
+                return component;
+            }
+        }
+
+        match (expr, self.react_use_key.take()) {
+            (Expr::Call(call), Some(key)) => self.react_use_component(key, call),
+            (expr, _) => expr,
+        }
+    }
+
+    fn fold_import_decl(&mut self, decl: ImportDecl) -> ImportDecl {
+        let ImportDecl {
+            ref src,
+            ref specifiers,
+            type_only,
+            ..
+        } = decl;
+        // Type-only imports have no runtime binding to transform.
+        if self.is_dynamic_specifier(&src.value) && !type_only {
+            for specifier in specifiers {
+                let local = match specifier {
+                    ImportSpecifier::Default(default_specifier) => &default_specifier.local,
+                    ImportSpecifier::Named(named_specifier) if !named_specifier.is_type_only => {
+                        let name = match &named_specifier.imported {
+                            Some(ModuleExportName::Ident(ident)) => &*ident.sym,
+                            Some(ModuleExportName::Str(str)) => &*str.value,
+                            None => &*named_specifier.local.sym,
+                        };
+                        // `import { default as dynamic } from 'next/dynamic'`
+                        if name == "default" {
+                            &named_specifier.local
+                        } else if &*src.value != "next/dynamic"
+                            || NEXT_DYNAMIC_NAMED_EXPORTS.contains(&name)
+                        {
+                            // Custom `Config::dynamic_specifiers` may have other named
+                            // exports.
+                            continue;
+                        } else {
+                            self.emit(
+                                DiagnosticLevel::Warning,
+                                named_specifier.span,
+                                Message::NamedImport { name },
+                            );
+                            continue;
+                        }
+                    }
+                    _ => continue,
+                };
+                debug_log!(
+                    filename = %self.filename,
+                    binding = %local.sym,
+                    "next_dynamic::binding_registered"
+                );
+                self.dynamic_bindings.push(local.to_id());
+            }
+        }
+
+        decl
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        self.stmt_depth += 1;
+        let stmt = stmt.fold_children_with(self);
+        self.stmt_depth -= 1;
+
+        stmt
+    }
+
+    fn fold_var_declarator(&mut self, decl: VarDeclarator) -> VarDeclarator {
+        let decl = decl.fold_children_with(self);
+
+        // CommonJS `const dynamic = require('next/dynamic')` form.
+        if let (Pat::Ident(name), Some(Expr::Call(init))) = (&decl.name, decl.init.as_deref()) {
+            let is_dynamic_require = [
+                interop::require_specifier(init),
+                interop::interop_require_default_specifier(init),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|specifier| self.is_dynamic_specifier(&specifier.value));
+            // Bindings required in a block, e.g. `if (isClient) { ... }`, are still
+            // registered, but the calls using them may not be found.
+            if is_dynamic_require && self.stmt_depth > 1 {
+                self.emit(
+                    DiagnosticLevel::Warning,
+                    init.span,
+                    Message::ConditionalImport,
+                );
+            }
+
+            if interop::require_specifier(init)
+                .is_some_and(|specifier| self.is_dynamic_specifier(&specifier.value))
+            {
+                debug_log!(
+                    filename = %self.filename,
+                    binding = %name.id.sym,
+                    "next_dynamic::binding_registered"
+                );
+                self.dynamic_bindings.push(name.id.to_id());
+            }
+
+            // `var _dynamic = _interopRequireDefault(require('next/dynamic'))`,
+            // or `const dynamic_1 = require("next/dynamic")` in ES modules
+            // compiled by TypeScript, called as `(0, _dynamic.default)(...)`.
+            let namespace_specifier = if self.is_compiled_es_module {
+                interop::require_specifier(init)
+            } else {
+                None
+            };
+            if namespace_specifier
+                .or_else(|| interop::interop_require_default_specifier(init))
+                .is_some_and(|specifier| self.is_dynamic_specifier(&specifier.value))
+            {
+                debug_log!(
+                    filename = %self.filename,
+                    binding = %name.id.sym,
+                    namespace = true,
+                    "next_dynamic::binding_registered"
+                );
+                self.dynamic_namespace_bindings.push(name.id.to_id());
+            }
+        }
+
+        decl
+    }
+
+    fn fold_call_expr(&mut self, expr: CallExpr) -> CallExpr {
+        if interop::define_property_export(&expr).is_some_and(|name| &*name.value == "__esModule") {
+            self.is_compiled_es_module = true;
+        }
+        // `import('next/dynamic')`, whose binding can't be seen.
+        if let (Callee::Import(..), Some(Expr::Lit(Lit::Str(specifier)))) =
+            (&expr.callee, expr.args.first().map(|arg| &*arg.expr))
+        {
+            if self.is_dynamic_specifier(&specifier.value) {
+                self.emit(
+                    DiagnosticLevel::Warning,
+                    expr.span,
+                    Message::ConditionalImport,
+                );
+            }
+        }
+        if self.is_next_dynamic_first_arg {
+            // Rspack shares the Webpack state, and understands the same magic
+            // comments.
+            if self.emit_magic_comments && matches!(self.state, NextDynamicPatcherState::Webpack) {
+                if let Some((pos, export)) = loader_export(&expr) {
+                    self.magic_comments
+                        .push((pos, format!("webpackExports: [\"{export}\"]")));
+                }
+            }
+            if let Callee::Import(..) = &expr.callee {
+                self.has_dynamic_import = true;
+                if self.config.babel_compat {
+                    self.babel_compat_imports.push(expr.args[0].expr.clone());
+                }
+                match &*expr.args[0].expr {
+                    Expr::Lit(Lit::Str(Str { value, span, .. })) => {
+                        self.dynamically_imported_specifier = Some((value.to_string(), *span));
+                    }
+                    Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => {
+                        self.dynamically_imported_specifier =
+                            Some((quasis[0].raw.to_string(), quasis[0].span));
+                    }
+                    _ => {}
+                }
+
+                if let Some((specifier, _)) = &self.dynamically_imported_specifier {
+                    if self
+                        .config
+                        .eager_specifiers
+                        .iter()
+                        .any(|pattern| paths::matches_pattern(pattern, specifier))
+                    {
+                        let specifier = specifier.clone();
+                        return self.eager_import(specifier);
+                    }
+                }
+            }
+            // The nested call is transformed on its own, without mixing up its `import()`
+            // with the outer call's.
+            if let Some(span) = match &expr.callee {
+                Callee::Expr(callee) => self.dynamic_callee_span(callee),
+                _ => None,
+            } {
+                self.emit(DiagnosticLevel::Error, span, Message::NestedCall);
+                let outer = (
+                    self.has_dynamic_import,
+                    self.is_eager,
+                    self.dynamically_imported_specifier.take(),
+                    std::mem::take(&mut self.babel_compat_imports),
+                );
+                self.is_next_dynamic_first_arg = false;
+                let expr = self.fold_call_expr(expr);
+                self.is_next_dynamic_first_arg = true;
+                (
+                    self.has_dynamic_import,
+                    self.is_eager,
+                    self.dynamically_imported_specifier,
+                    self.babel_compat_imports,
+                ) = outer;
+                return expr;
+            }
+            return expr.fold_children_with(self);
+        }
+        let callee_span = match &expr.callee {
+            Callee::Expr(callee) => self.dynamic_callee_span(callee),
+            _ => None,
+        };
+        // The arguments of `dynamic()` calls are folded once the call is validated, so
+        // that the loader is only traversed once, with `is_next_dynamic_first_arg` set.
+        let mut expr = if callee_span.is_some() {
+            expr
+        } else {
+            expr.fold_children_with(self)
+        };
+        if let Callee::Expr(callee) = &expr.callee {
+            if let Some(span) = self.invoked_dynamic_call_span(callee) {
+                self.emit(DiagnosticLevel::Warning, span, Message::InvokedComponent);
+            }
+        }
+        if let Some(span) = callee_span {
+            debug_log!(
+                filename = %self.filename,
+                lo = span.lo.0,
+                "next_dynamic::call_matched"
+            );
+            if self.config.is_edge_entry {
+                self.emit(DiagnosticLevel::Error, span, Message::EdgeEntry);
+                return expr.fold_children_with(self);
+            }
+            if expr.args.is_empty() {
+                self.emit(DiagnosticLevel::Error, span, Message::NoArguments);
+                return expr.fold_children_with(self);
+            } else if expr.args.len() > 2 {
+                self.emit(DiagnosticLevel::Error, span, Message::TooManyArguments);
+                return expr.fold_children_with(self);
+            }
+            if expr.args.len() == 2 {
+                match options_object(&expr.args[1].expr) {
+                    Some(options) => {
+                        for diagnostic in options::check_options(&options.props) {
+                            self.emit_diagnostic(diagnostic);
+                        }
+                    }
+                    None => {
+                        self.emit(DiagnosticLevel::Error, span, Message::InvalidOptionsType);
+                        return expr.fold_children_with(self);
+                    }
+                }
+            }
+
+            if let Some(specifier) = inline_loader_specifier(&expr.args[0].expr) {
+                let module = if self.config.test_stub {
+                    Some(quote!(
+                        "require($specifier)" as Expr,
+                        specifier: Expr = specifier.to_string().into(),
+                    ))
+                } else if self
+                    .config
+                    .inline_specifiers
+                    .iter()
+                    .any(|pattern| paths::matches_pattern(pattern, specifier))
+                {
+                    Some(Expr::Ident(self.eager_namespace(specifier.to_string())))
+                } else {
+                    None
+                };
+
+                if let Some(module) = module {
+                    self.inline_component =
+                        Some(self.sync_component(module, expr.type_args.as_deref()));
+                    self.stats.calls_transformed += 1;
+                    return expr;
+                }
+            }
+
+            self.is_next_dynamic_first_arg = true;
+            self.has_dynamic_import = false;
+            self.is_eager = false;
+            self.babel_compat_imports.clear();
+            if let Expr::Object(legacy_options) = &mut *expr.args[0].expr {
+                // Legacy `dynamic({ loader: () => import('...'), ...options })` form,
+                // which is still supported by the runtime.
+                legacy_options.props = std::mem::take(&mut legacy_options.props)
+                    .into_iter()
+                    .map(|prop| {
+                        self.is_next_dynamic_first_arg = is_key_value_prop(&prop, "loader");
+                        prop.fold_with(self)
+                    })
+                    .collect();
+            } else {
+                expr.args[0].expr = expr.args[0].expr.take().fold_with(self);
+            }
+            self.is_next_dynamic_first_arg = false;
+            // The options can contain `dynamic()` calls too, e.g. for the
+            // `loading` component, which mustn't clobber the `import()` of
+            // this call.
+            let loader = (
+                self.has_dynamic_import,
+                self.is_eager,
+                self.dynamically_imported_specifier.take(),
+                std::mem::take(&mut self.babel_compat_imports),
+            );
+            expr.callee = expr.callee.fold_with(self);
+            for arg in expr.args.iter_mut().skip(1) {
+                arg.expr = arg.expr.take().fold_with(self);
+            }
+            (
+                self.has_dynamic_import,
+                self.is_eager,
+                self.dynamically_imported_specifier,
+                self.babel_compat_imports,
+            ) = loader;
+
+            if self.config.babel_compat && matches!(self.state, NextDynamicPatcherState::Webpack) {
+                self.dynamically_imported_specifier = None;
+                let imports = std::mem::take(&mut self.babel_compat_imports);
+                if self.babel_compat_call(&mut expr, imports) {
+                    self.stats.calls_transformed += 1;
+                }
+                return expr;
+            }
+
+            // The module is part of the current chunk, so there is nothing to preload.
+            if self.is_eager {
+                self.dynamically_imported_specifier = None;
+                self.stats.calls_transformed += 1;
+                return expr;
+            }
+
+            if self.config.is_amp_only {
+                self.dynamically_imported_specifier = None;
+                return expr;
+            }
+
+            let Some((dynamically_imported_specifier, dynamically_imported_specifier_span)) =
+                self.dynamically_imported_specifier.take()
+            else {
+                // Non-static `import()` specifiers can't be tracked, but are otherwise
+                // valid.
+                if !self.has_dynamic_import {
+                    // Lets `next/dynamic` log the warning in the browser console
+                    // too, where the component is created.
+                    if self.is_development {
+                        let placeholder = LoadableGenerated::new()
+                            .extend(placeholder_options(&Message::NoImportInLoader.to_string()))
+                            .into_prop();
+                        match expr
+                            .args
+                            .get_mut(1)
+                            .and_then(|arg| options_object_mut(&mut arg.expr))
+                        {
+                            Some(options) => options.props.insert(0, placeholder),
+                            _ => expr.args.push(
+                                Expr::Object(ObjectLit {
+                                    span: DUMMY_SP,
+                                    props: vec![placeholder],
+                                })
+                                .as_arg(),
+                            ),
+                        }
+                    }
+                    self.emit(DiagnosticLevel::Warning, span, Message::NoImportInLoader);
+                }
+                return expr;
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                specifier = %dynamically_imported_specifier,
+                "next_dynamic::call"
+            );
+
+            if let Some(event_sink) = &self.config.event_sink {
+                event_sink.on_call_detected(
+                    &self.filename,
+                    &dynamically_imported_specifier,
+                    dynamically_imported_specifier_span,
+                );
+            }
+
+            let module_id = self
+                .config
+                .module_id_resolver
+                .as_ref()
+                .and_then(|resolver| {
+                    resolver.resolve(&dynamically_imported_specifier, &self.filename)
+                })
+                .unwrap_or_else(|| dynamically_imported_specifier.clone());
+
+            // The module id used in the React Loadable Manifest key.
+            let key_module_id = paths::expand_path_alias(&self.config.paths, &module_id)
+                .map(|target| match (&self.config.base_url, &self.filename) {
+                    (Some(base_url), FileName::Real(filename)) => {
+                        paths::relative_specifier(base_url, &target, filename).unwrap_or(target)
+                    }
+                    _ => target,
+                })
+                .unwrap_or_else(|| module_id.clone());
+
+            let has_webpack_keys = self.webpack_delta.is_some()
+                || matches!(
+                    self.state,
+                    NextDynamicPatcherState::Webpack
+                        | NextDynamicPatcherState::Turbopack { hybrid: true, .. }
+                );
+            if has_webpack_keys {
+                self.register_loadable_key(
+                    &key_module_id,
+                    &dynamically_imported_specifier,
+                    dynamically_imported_specifier_span,
+                );
+            }
+
+            // dev client or server:
+            // loadableGenerated: {
+            //   modules:
+            // ["/project/src/file-being-transformed.js -> " + '../components/hello'] }
+
+            // prod client
+            // loadableGenerated: {
+            //   webpack: () => [require.resolveWeak('../components/hello')],
+            let webpack_generated = || {
+                if self.is_development || self.is_server_compiler {
+                    let key_filename = self.key_filename().0;
+                    let key = match self.emitted_key(&format!("{key_filename} -> {key_module_id}"))
+                    {
+                        Some(emitted_key) => Expr::from(emitted_key),
+                        None => quote!(
+                            "$left + $right" as Expr,
+                            left: Expr = format!("{key_filename} -> ").into(),
+                            right: Expr = key_module_id.clone().into(),
+                        ),
+                    };
+                    let chunks = match &self.config.chunk_resolver {
+                        Some(chunk_resolver) if self.is_development => {
+                            chunk_resolver.resolve(&module_id)
+                        }
+                        _ => None,
+                    };
+
+                    match chunks {
+                        // Like in Turbopack development mode, the chunks are passed
+                        // along with the key, so the runtime doesn't need to look them
+                        // up in the React Loadable Manifest.
+                        Some(chunks) => module_id_options(quote!(
+                            r#"
+                                    JSON.stringify({
+                                        id: $id,
+                                        chunks: $chunks
+                                    })
+                                    "# as Expr,
+                            id: Expr = key,
+                            chunks: Expr = Expr::Array(ArrayLit {
+                                span: DUMMY_SP,
+                                elems: chunks
+                                    .into_iter()
+                                    .map(|chunk| Some(Expr::from(chunk).as_arg()))
+                                    .collect(),
+                            }),
+                        )),
+                        None => module_id_options(key),
+                    }
+                } else {
+                    webpack_options(resolve_module_id(
+                        self.config.webpack_resolver,
+                        module_id.clone().into(),
+                    ))
+                }
+            };
+            debug_log!(
+                filename = %self.filename,
+                specifier = %module_id,
+                backend = self.state.name(),
+                "next_dynamic::backend"
+            );
+            // The props of the Webpack output recorded in the `webpack_delta`.
+            let records_webpack_delta = self.webpack_delta.is_some();
+            let mut webpack_delta_props = None;
+            let generated = LoadableGenerated::new().extend(match &mut self.state {
+                NextDynamicPatcherState::Webpack => webpack_generated(),
+                NextDynamicPatcherState::Custom { codegen, items } => {
+                    let output = codegen.generate(&DynamicCallContext {
+                        module_id: &module_id,
+                        span: dynamically_imported_specifier_span,
+                        filename: &self.filename,
+                        pages_dir: self.pages_dir.as_deref(),
+                        is_development: self.is_development,
+                        is_server_compiler: self.is_server_compiler,
+                        is_react_server_layer: self.is_react_server_layer,
+                    });
+                    items.extend(output.module_items);
+                    output.props
+                }
+                NextDynamicPatcherState::Turbopack {
+                    imports, hybrid, ..
+                } => {
+                    let webpack_props = (*hybrid || records_webpack_delta).then(webpack_generated);
+
+                    // Calls importing the same specifier share a single import, so
+                    // the module is only registered once as a loadable.
+                    let import = match imports
+                        .iter()
+                        .find(|import| import.specifier() == module_id)
+                    {
+                        Some(import) => import.clone(),
+                        None => {
+                            let import = TurbopackImport::new(
+                                self.is_development,
+                                self.is_server_compiler,
+                                !self.config.non_streaming,
+                                module_id.clone(),
+                                dynamically_imported_specifier_span,
+                            );
+                            // Calls in the options of another call are folded before it, so
+                            // the imports are kept in the source order of the calls instead.
+                            let index = imports.partition_point(|import| {
+                                import.span().lo <= dynamically_imported_specifier_span.lo
+                            });
+                            imports.insert(index, import.clone());
+                            import
+                        }
+                    };
+
+                    let turbopack_props = match import {
+                        TurbopackImport::DevelopmentTransition {
+                            id_ident,
+                            chunks_ident: Some(chunks_ident),
+                            ..
+                        } => {
+                            // On the server, the key needs to be serialized because it
+                            // will be used to index the React Loadable Manifest, which
+                            // is a normal JS object. In Turbo mode, this is a proxy,
+                            // but the key will still be coerced to a string.
+                            module_id_options(quote!(
+                                r#"
+                                            JSON.stringify({
+                                                id: $id,
+                                                chunks: $chunks
+                                            })
+                                            "# as Expr,
+                                id = id_ident,
+                                chunks = chunks_ident,
+                            ))
+                        }
+                        // On the client, we only need the target module ID, which
+                        // will be reported under the `dynamicIds` property of Next
+                        // data. Without streaming, neither does the server.
+                        TurbopackImport::DevelopmentTransition { id_ident, .. }
+                        | TurbopackImport::DevelopmentId { id_ident, .. }
+                        | TurbopackImport::BuildTransition { id_ident, .. }
+                        | TurbopackImport::BuildId { id_ident, .. } => {
+                            module_id_options(Expr::Ident(id_ident))
+                        }
+                    };
+
+                    match webpack_props {
+                        Some(webpack_props) if *hybrid => {
+                            merge_module_id_options(webpack_props, turbopack_props)
+                        }
+                        webpack_props => {
+                            webpack_delta_props = webpack_props;
+                            turbopack_props
+                        }
+                    }
+                }
+            });
+
+            if self.config.react_use {
+                self.react_use_key = loadable_key(generated.props());
+            }
+
+            let mut generated = generated.build();
+            let mut webpack_delta_generated =
+                webpack_delta_props.map(|props| LoadableGenerated::new().extend(props).build());
+            if let Some(hook) = &self.config.transform_generated {
+                let call = DynamicCallContext {
+                    module_id: &module_id,
+                    span: dynamically_imported_specifier_span,
+                    filename: &self.filename,
+                    pages_dir: self.pages_dir.as_deref(),
+                    is_development: self.is_development,
+                    is_server_compiler: self.is_server_compiler,
+                    is_react_server_layer: self.is_react_server_layer,
+                };
+                hook.transform(&mut generated, &call);
+                if let Some(webpack_delta_generated) = &mut webpack_delta_generated {
+                    hook.transform(webpack_delta_generated, &call);
+                }
+            }
+            if let (Some(webpack_delta), Some(webpack_delta_generated)) =
+                (&mut self.webpack_delta, webpack_delta_generated)
+            {
+                generated.span = DUMMY_SP.with_ctxt(webpack_delta.ctxt);
+                webpack_delta
+                    .loadable_generated
+                    .push(webpack_delta_generated);
+            }
+
+            let mut props = vec![loadable_generated_prop(generated)];
+
+            for arg in expr.args.iter_mut() {
+                if let Some(options) = options_object_mut(&mut arg.expr) {
+                    fold_ssr_option(options, &self.config.env, &self.declared_bindings);
+                }
+            }
+
+            let mut options = DynamicOptions::default();
+
+            if let Expr::Object(ObjectLit {
+                props: legacy_options_props,
+                ..
+            }) = &*expr.args[0].expr
+            {
+                options.scan(legacy_options_props);
+            }
+
+            if expr.args.len() == 2 {
+                if let Some(ObjectLit {
+                    props: options_props,
+                    ..
+                }) = options_object(&expr.args[1].expr)
+                {
+                    options.scan(options_props);
+                    props.extend(options_props.iter().cloned());
+                }
+            }
+
+            // The loading hints are only understood by the transform, which turns
+            // them into Webpack magic comments. They're kept when no comment is
+            // added, so that they aren't silently dropped.
+            if self.emit_magic_comments && matches!(self.state, NextDynamicPatcherState::Webpack) {
+                props.retain(|prop| {
+                    !is_key_value_prop(prop, "prefetch") && !is_key_value_prop(prop, "preload")
+                });
+                let pos = dynamically_imported_specifier_span.lo;
+                if options.has_prefetch {
+                    self.magic_comments
+                        .push((pos, "webpackPrefetch: true".into()));
+                }
+                if options.has_preload {
+                    self.magic_comments
+                        .push((pos, "webpackPreload: true".into()));
+                }
+            }
+
+            // Options spread from another object may set `ssr` themselves, in which case
+            // they take precedence over the default inserted before them.
+            if !options.has_ssr {
+                if let Some(ssr) = self.config.default_ssr {
+                    props.insert(
+                        1,
+                        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                            key: PropName::Ident(Ident::new("ssr".into(), DUMMY_SP)),
+                            value: Box::new(Expr::Lit(Lit::Bool(Bool {
+                                span: DUMMY_SP,
+                                value: ssr,
+                            }))),
+                        }))),
+                    );
+                    options.has_ssr_false = !ssr && !options.has_spread;
+                }
+            }
+
+            if let Some(dynamic_calls) = &self.config.dynamic_calls {
+                dynamic_calls.lock().unwrap().push(DynamicCall {
+                    specifier: dynamically_imported_specifier.clone(),
+                    file: self.filename.to_string(),
+                    ssr: !options.has_ssr_false,
+                });
+            }
+
+            // Don't strip the `loader` argument if suspense is true
+            // See https://github.com/vercel/next.js/issues/36636 for background.
+
+            // Also don't strip the `loader` argument for server components (both
+            // server/client layers), since they're aliased to a
+            // React.lazy implementation.
+            if options.has_ssr_false
+                && !options.has_suspense
+                && self.is_server_compiler
+                && !self.is_react_server_layer
+            {
+                if let Some(event_sink) = &self.config.event_sink {
+                    event_sink.on_loader_stripped(&self.filename, span);
+                }
+
+                if let Expr::Object(legacy_options) = &mut *expr.args[0].expr {
+                    legacy_options
+                        .props
+                        .retain(|prop| !is_key_value_prop(prop, "loader"));
+                } else if self.config.preserve_types {
+                    // Unlike `null`, `null!` is assignable to the type of the loader.
+                    expr.args[0] = Expr::TsNonNull(TsNonNullExpr {
+                        span: DUMMY_SP,
+                        expr: Lit::Null(Null { span: DUMMY_SP }).into(),
+                    })
+                    .as_arg();
+                } else {
+                    expr.args[0] = Lit::Null(Null { span: DUMMY_SP }).as_arg();
+                }
+            }
+
+            let options = ObjectLit {
+                span: DUMMY_SP,
+                props,
+            };
+            // The TypeScript wrappers of the options, e.g. `satisfies DynamicOptions`,
+            // are kept around the new object.
+            match expr
+                .args
+                .get_mut(1)
+                .and_then(|arg| options_object_mut(&mut arg.expr))
+            {
+                Some(existing) => *existing = options,
+                None => expr.args.push(Expr::Object(options).as_arg()),
+            }
+
+            self.stats.calls_transformed += 1;
+        }
+        expr
+    }
+}
+
+/// The options of a `dynamic()` call which affect the transform.
+#[derive(Debug, Default)]
+struct DynamicOptions {
+    has_ssr: bool,
+    has_ssr_false: bool,
+    has_suspense: bool,
+    has_spread: bool,
+    has_prefetch: bool,
+    has_preload: bool,
+}
+
+impl DynamicOptions {
+    fn scan(&mut self, props: &[PropOrSpread]) {
+        for prop in props.iter() {
+            if let PropOrSpread::Spread(..) = prop {
+                self.has_spread = true;
+                // The spread object can set `ssr` itself at runtime, overriding the
+                // literal before it, so the loader must be kept.
+                self.has_ssr_false = false;
+            }
+            if let Some(KeyValueProp { key, value }) = match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(key_value_prop) => Some(key_value_prop),
+                    _ => None,
+                },
+                _ => None,
+            } {
+                if let Some(sym) = static_prop_name(key) {
+                    // Like in any object literal, the last value of an option wins.
+                    if sym == "ssr" {
+                        self.has_ssr = true;
+                        self.has_ssr_false =
+                            matches!(value.as_lit(), Some(Lit::Bool(Bool { value: false, .. })));
+                    }
+                    if sym == "suspense" {
+                        if let Some(Lit::Bool(Bool {
+                            value: true,
+                            span: _,
+                        })) = value.as_lit()
+                        {
+                            self.has_suspense = true
+                        }
+                    }
+                    if sym == "prefetch" || sym == "preload" {
+                        if let Some(Lit::Bool(Bool {
+                            value: true,
+                            span: _,
+                        })) = value.as_lit()
+                        {
+                            if sym == "prefetch" {
+                                self.has_prefetch = true
+                            } else {
+                                self.has_preload = true
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the value of the `ssr` option with a literal when it can be
+/// evaluated at compile time.
+fn fold_ssr_option(
+    options: &mut ObjectLit,
+    env: &HashMap<String, String>,
+    declared_bindings: &AHashSet<Id>,
+) {
+    for prop in options.props.iter_mut() {
+        if let PropOrSpread::Prop(prop) = prop {
+            if let Prop::KeyValue(KeyValueProp { key, value }) = &mut **prop {
+                if static_prop_name(key) == Some("ssr") && value.as_lit().is_none() {
+                    if let Some(ssr) = const_eval::eval_bool(value, env, declared_bindings) {
+                        *value = Box::new(Expr::Lit(Lit::Bool(Bool {
+                            span: value.span(),
+                            value: ssr,
+                        })));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the `loader` property of the legacy `dynamic({ loader, ...options
+/// })` form.
+fn legacy_loader(options: &mut ObjectLit) -> Option<&mut Box<Expr>> {
+    options.props.iter_mut().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &mut **prop {
+            Prop::KeyValue(KeyValueProp { key, value })
+                if static_prop_name(key) == Some("loader") =>
+            {
+                Some(value)
+            }
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Returns the value of the `name` property of `options`, if any.
+fn key_value_prop<'a>(options: &'a ObjectLit, name: &str) -> Option<&'a Expr> {
+    options.props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, value }) if static_prop_name(key) == Some(name) => {
+                Some(&**value)
+            }
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+pub(crate) fn is_key_value_prop(prop: &PropOrSpread, name: &str) -> bool {
+    match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, .. }) => static_prop_name(key) == Some(name),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns the object literal of the options of a `dynamic()` call, through
+/// the TypeScript-only wrappers it may be in, e.g.
+/// `{ ssr: false } satisfies DynamicOptions`.
+pub(crate) fn options_object(expr: &Expr) -> Option<&ObjectLit> {
+    match expr {
+        Expr::Object(options) => Some(options),
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+        | Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. }) => options_object(expr),
+        _ => None,
+    }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum TurbopackImport {
-    DevelopmentTransition {
-        id_ident: Ident,
-        chunks_ident: Ident,
-        specifier: String,
-    },
-    DevelopmentId {
-        id_ident: Ident,
-        specifier: String,
-    },
-    BuildTransition {
-        id_ident: Ident,
-        specifier: String,
-    },
-    BuildId {
-        id_ident: Ident,
-        specifier: String,
-    },
+fn options_object_mut(expr: &mut Expr) -> Option<&mut ObjectLit> {
+    match expr {
+        Expr::Object(options) => Some(options),
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+        | Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. }) => options_object_mut(expr),
+        _ => None,
+    }
 }
 
-impl Fold for NextDynamicPatcher {
-    fn fold_module_items(&mut self, mut items: Vec<ModuleItem>) -> Vec<ModuleItem> {
-        items = items.fold_children_with(self);
-
-        self.maybe_add_dynamically_imported_specifier(&mut items);
+/// Returns the name of a property key known at compile time, e.g. `ssr` for
+/// `ssr`, `'ssr'` and `['ssr']`.
+pub(crate) fn static_prop_name(key: &PropName) -> Option<&str> {
+    match key {
+        PropName::Ident(ident) => Some(&ident.sym),
+        PropName::Str(str) => Some(&str.value),
+        PropName::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(str)) => Some(&str.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
 
-        items
+impl NextDynamicPatcher {
+    /// Returns whether `specifier` imports `next/dynamic`.
+    fn is_dynamic_specifier(&self, specifier: &str) -> bool {
+        self.config.is_dynamic_specifier(specifier)
     }
 
-    fn fold_import_decl(&mut self, decl: ImportDecl) -> ImportDecl {
-        let ImportDecl {
-            ref src,
-            ref specifiers,
-            ..
-        } = decl;
-        if &src.value == "next/dynamic" {
-            for specifier in specifiers {
-                if let ImportSpecifier::Default(default_specifier) = specifier {
-                    self.dynamic_bindings.push(default_specifier.local.to_id());
+    /// Returns the span of `callee` when it's `dynamic`, possibly wrapped in a
+    /// `(0, dynamic)` sequence or in `Config::transparent_wrappers` calls by
+    /// other transforms or instrumentation.
+    fn dynamic_callee_span(&self, callee: &Expr) -> Option<Span> {
+        match callee {
+            Expr::Ident(ident) if self.dynamic_bindings.contains(&ident.to_id()) => {
+                Some(ident.span)
+            }
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop: MemberProp::Ident(prop),
+            }) if &*prop.sym == "default" => match &**obj {
+                Expr::Ident(obj) if self.dynamic_namespace_bindings.contains(&obj.to_id()) => {
+                    Some(*span)
                 }
+                _ => None,
+            },
+            Expr::Paren(ParenExpr { expr, .. }) => self.dynamic_callee_span(expr),
+            Expr::Seq(SeqExpr { exprs, .. })
+                if exprs.len() == 2 && matches!(&*exprs[0], Expr::Lit(Lit::Num(..))) =>
+            {
+                self.dynamic_callee_span(&exprs[1])
             }
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(wrapper),
+                args,
+                ..
+            }) if args.len() == 1 => match &**wrapper {
+                Expr::Ident(wrapper)
+                    if self
+                        .config
+                        .transparent_wrappers
+                        .iter()
+                        .any(|name| *name == *wrapper.sym) =>
+                {
+                    self.dynamic_callee_span(&args[0].expr)
+                }
+                _ => None,
+            },
+            _ => None,
         }
-
-        decl
     }
 
-    fn fold_call_expr(&mut self, expr: CallExpr) -> CallExpr {
-        if self.is_next_dynamic_first_arg {
-            if let Callee::Import(..) = &expr.callee {
-                match &*expr.args[0].expr {
-                    Expr::Lit(Lit::Str(Str { value, span, .. })) => {
-                        self.dynamically_imported_specifier = Some((value.to_string(), *span));
-                    }
-                    Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => {
-                        self.dynamically_imported_specifier =
-                            Some((quasis[0].raw.to_string(), quasis[0].span));
+    /// Returns the span of the `dynamic` callee when `callee` is a `dynamic()`
+    /// call, or a `memo()` or `forwardRef()` call wrapping one, i.e. when the
+    /// created component is invoked instead of being rendered.
+    fn invoked_dynamic_call_span(&self, callee: &Expr) -> Option<Span> {
+        match callee {
+            Expr::Paren(ParenExpr { expr, .. }) => self.invoked_dynamic_call_span(expr),
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) => {
+                if let Some(span) = self.dynamic_callee_span(callee) {
+                    return Some(span);
+                }
+                let is_component_wrapper = match &**callee {
+                    Expr::Ident(Ident { sym, .. })
+                    | Expr::Member(MemberExpr {
+                        prop: MemberProp::Ident(Ident { sym, .. }),
+                        ..
+                    }) => matches!(&**sym, "memo" | "forwardRef"),
+                    _ => false,
+                };
+                match args.first() {
+                    Some(arg) if is_component_wrapper && arg.spread.is_none() => {
+                        self.invoked_dynamic_call_span(&arg.expr)
                     }
-                    _ => {}
+                    _ => None,
                 }
             }
-            return expr.fold_children_with(self);
+            _ => None,
         }
-        let mut expr = expr.fold_children_with(self);
-        if let Callee::Expr(i) = &expr.callee {
-            if let Expr::Ident(identifier) = &**i {
-                if self.dynamic_bindings.contains(&identifier.to_id()) {
-                    if expr.args.is_empty() {
-                        HANDLER.with(|handler| {
-                            handler
-                                .struct_span_err(
-                                    identifier.span,
-                                    "next/dynamic requires at least one argument",
-                                )
-                                .emit()
-                        });
-                        return expr;
-                    } else if expr.args.len() > 2 {
-                        HANDLER.with(|handler| {
-                            handler
-                                .struct_span_err(
-                                    identifier.span,
-                                    "next/dynamic only accepts 2 arguments",
-                                )
-                                .emit()
-                        });
-                        return expr;
-                    }
-                    if expr.args.len() == 2 {
-                        match &*expr.args[1].expr {
-                            Expr::Object(_) => {}
-                            _ => {
-                                HANDLER.with(|handler| {
-                          handler
-                              .struct_span_err(
-                                  identifier.span,
-                                  "next/dynamic options must be an object literal.\nRead more: https://nextjs.org/docs/messages/invalid-dynamic-options-type",
-                              )
-                              .emit();
-                      });
-                                return expr;
-                            }
-                        }
-                    }
+    }
+
+    /// Returns the key emitted in place of `key`, when it's longer than
+    /// `Config::max_key_length` or when `Config::minify_keys` is set.
+    fn emitted_key(&self, key: &str) -> Option<String> {
+        let is_minified = self.config.minify_keys && !self.is_development;
+        (is_minified
+            || self
+                .config
+                .max_key_length
+                .is_some_and(|max_key_length| key.len() > max_key_length))
+        .then(|| hashed_key(key))
+    }
 
-                    self.is_next_dynamic_first_arg = true;
-                    expr.args[0].expr = expr.args[0].expr.clone().fold_with(self);
-                    self.is_next_dynamic_first_arg = false;
+    /// Returns the path of the module used in React Loadable Manifest keys,
+    /// along with the directory it's relative to.
+    fn key_filename(&self) -> (String, KeyBase) {
+        if let (Some(root_dir), Some(pages_dir), FileName::Real(filename)) =
+            (&self.config.root_dir, &self.pages_dir, &self.filename)
+        {
+            if !filename.starts_with(pages_dir) {
+                return (
+                    rel_filename(Some(root_dir), &self.filename),
+                    KeyBase::RootDir,
+                );
+            }
+        }
 
-                    let Some((dynamically_imported_specifier, dynamically_imported_specifier_span)) =
-                        self.dynamically_imported_specifier.take()
-                    else {
-                        return expr;
-                    };
+        (
+            rel_filename(self.pages_dir.as_deref(), &self.filename),
+            KeyBase::PagesDir,
+        )
+    }
 
-                    // dev client or server:
-                    // loadableGenerated: {
-                    //   modules:
-                    // ["/project/src/file-being-transformed.js -> " + '../components/hello'] }
+    /// Records the React Loadable Manifest key generated for a `dynamic()`
+    /// call, and reports keys colliding with the key of another call, either
+    /// in this module or, when a registry is provided, in another module.
+    fn register_loadable_key(&mut self, module_id: &str, specifier: &str, span: Span) {
+        let (key_filename, key_base) = self.key_filename();
+        let key = format!("{key_filename} -> {module_id}");
+        let emitted_key = self.emitted_key(&key);
 
-                    // prod client
-                    // loadableGenerated: {
-                    //   webpack: () => [require.resolveWeak('../components/hello')],
-                    let generated = Box::new(Expr::Object(ObjectLit {
-                        span: DUMMY_SP,
-                        props: match &mut self.state {
-                            NextDynamicPatcherState::Webpack => {
-                                if self.is_development || self.is_server_compiler {
-                                    module_id_options(quote!(
-                                        "$left + $right" as Expr,
-                                        left: Expr = format!(
-                                            "{} -> ",
-                                            rel_filename(self.pages_dir.as_deref(), &self.filename)
-                                        )
-                                        .into(),
-                                        right: Expr = dynamically_imported_specifier.into(),
-                                    ))
-                                } else {
-                                    webpack_options(quote!(
-                                        "require.resolveWeak($id)" as Expr,
-                                        id: Expr = dynamically_imported_specifier.into()
-                                    ))
-                                }
-                            }
-                            NextDynamicPatcherState::Turbopack { imports, .. } => {
-                                let id_ident =
-                                    private_ident!(dynamically_imported_specifier_span, "id");
-
-                                match (self.is_development, self.is_server_compiler) {
-                                    (true, true) => {
-                                        let chunks_ident = private_ident!(
-                                            dynamically_imported_specifier_span,
-                                            "chunks"
-                                        );
-
-                                        imports.push(TurbopackImport::DevelopmentTransition {
-                                            id_ident: id_ident.clone(),
-                                            chunks_ident: chunks_ident.clone(),
-                                            specifier: dynamically_imported_specifier,
-                                        });
-
-                                        // On the server, the key needs to be serialized because it
-                                        // will be used to index the React Loadable Manifest, which
-                                        // is a normal JS object. In Turbo mode, this is a proxy,
-                                        // but the key will still be coerced to a string.
-                                        module_id_options(quote!(
-                                            r#"
-                                            JSON.stringify({
-                                                id: $id,
-                                                chunks: $chunks
-                                            })
-                                            "# as Expr,
-                                            id = id_ident,
-                                            chunks = chunks_ident,
-                                        ))
-                                    }
-                                    (true, false) => {
-                                        imports.push(TurbopackImport::DevelopmentId {
-                                            id_ident: id_ident.clone(),
-                                            specifier: dynamically_imported_specifier,
-                                        });
-
-                                        // On the client, we only need the target module ID, which
-                                        // will be reported under the `dynamicIds` property of Next
-                                        // data.
-                                        module_id_options(Expr::Ident(id_ident))
-                                    }
-                                    (false, true) => {
-                                        let id_ident = private_ident!(
-                                            dynamically_imported_specifier_span,
-                                            "id"
-                                        );
-
-                                        imports.push(TurbopackImport::BuildTransition {
-                                            id_ident: id_ident.clone(),
-                                            specifier: dynamically_imported_specifier.clone(),
-                                        });
-
-                                        module_id_options(Expr::Ident(id_ident))
-                                    }
-                                    (false, false) => {
-                                        let id_ident = private_ident!(
-                                            dynamically_imported_specifier_span,
-                                            "id"
-                                        );
-
-                                        imports.push(TurbopackImport::BuildId {
-                                            id_ident: id_ident.clone(),
-                                            specifier: dynamically_imported_specifier.clone(),
-                                        });
-
-                                        module_id_options(Expr::Ident(id_ident))
-                                    }
-                                }
-                            }
-                        },
-                    }));
+        if let Some(event_sink) = &self.config.event_sink {
+            event_sink.on_key_generated(&self.filename, &key, span);
+        }
 
-                    let mut props =
-                        vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                            key: PropName::Ident(Ident::new("loadableGenerated".into(), DUMMY_SP)),
-                            value: generated,
-                        })))];
+        // Keys are compared case-insensitively, as they will point to the same
+        // file on case-insensitive filesystems.
+        let collision = match self.generated_keys.entry(key.to_lowercase()) {
+            Entry::Occupied(entry) => {
+                let (existing_key, existing_module_id) = entry.get();
+                (existing_module_id != module_id)
+                    .then(|| (existing_key.clone(), existing_module_id.clone()))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((key.clone(), module_id.to_string()));
 
-                    let mut has_ssr_false = false;
-                    let mut has_suspense = false;
+                if let Some(manifest_entries) = &self.config.manifest_entries {
+                    let (key, original_key) = match emitted_key {
+                        Some(emitted_key) => (emitted_key, Some(key.clone())),
+                        None => (key.clone(), None),
+                    };
+                    manifest_entries.lock().unwrap().push(ManifestEntry {
+                        key,
+                        specifier: specifier.to_string(),
+                        file: self.filename.to_string(),
+                        key_base,
+                        original_key,
+                    });
+                }
 
-                    if expr.args.len() == 2 {
-                        if let Expr::Object(ObjectLit {
-                            props: options_props,
-                            ..
-                        }) = &*expr.args[1].expr
-                        {
-                            for prop in options_props.iter() {
-                                if let Some(KeyValueProp { key, value }) = match prop {
-                                    PropOrSpread::Prop(prop) => match &**prop {
-                                        Prop::KeyValue(key_value_prop) => Some(key_value_prop),
-                                        _ => None,
-                                    },
-                                    _ => None,
-                                } {
-                                    if let Some(Ident {
-                                        sym,
-                                        span: _,
-                                        optional: _,
-                                    }) = match key {
-                                        PropName::Ident(ident) => Some(ident),
-                                        _ => None,
-                                    } {
-                                        if sym == "ssr" {
-                                            if let Some(Lit::Bool(Bool {
-                                                value: false,
-                                                span: _,
-                                            })) = value.as_lit()
-                                            {
-                                                has_ssr_false = true
-                                            }
-                                        }
-                                        if sym == "suspense" {
-                                            if let Some(Lit::Bool(Bool {
-                                                value: true,
-                                                span: _,
-                                            })) = value.as_lit()
-                                            {
-                                                has_suspense = true
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            props.extend(options_props.iter().cloned());
-                        }
-                    }
+                None
+            }
+        };
+        if let Some((existing_key, existing_module_id)) = collision {
+            let mut diagnostic = DynamicDiagnostic::new(
+                DiagnosticLevel::Warning,
+                span,
+                Message::KeyCollision {
+                    key: &key,
+                    existing_key: &existing_key,
+                    existing_module_id: &existing_module_id,
+                },
+            );
+            diagnostic.specifier = Some(specifier.to_string());
+            self.emit_diagnostic(diagnostic);
+        }
+
+        let registry_collision = self
+            .config
+            .key_registry
+            .as_ref()
+            .and_then(|registry| registry.check(&key, &self.filename, specifier, span));
+        if let Some(diagnostic) = registry_collision {
+            self.emit_diagnostic(diagnostic);
+        }
+        if let Some(registered_keys) = &mut self.registered_keys {
+            registered_keys.push((key, specifier.to_string(), span));
+        }
+    }
 
-                    // Don't strip the `loader` argument if suspense is true
-                    // See https://github.com/vercel/next.js/issues/36636 for background.
+    /// Transforms a `dynamic()` call like the legacy `next/babel` plugin, given
+    /// the arguments of the `import()` expressions of its loader. Returns
+    /// whether the `loadableGenerated` option was added.
+    fn babel_compat_call(&self, expr: &mut CallExpr, imports: Vec<Box<Expr>>) -> bool {
+        let is_legacy = expr.args[0].expr.is_object();
+        if !is_legacy && expr.args.len() == 1 {
+            expr.args.push(
+                Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                })
+                .as_arg(),
+            );
+        }
 
-                    // Also don't strip the `loader` argument for server components (both
-                    // server/client layers), since they're aliased to a
-                    // React.lazy implementation.
-                    if has_ssr_false
-                        && !has_suspense
-                        && self.is_server_compiler
-                        && !self.is_react_server_layer
-                    {
-                        expr.args[0] = Lit::Null(Null { span: DUMMY_SP }).as_arg();
-                    }
+        // The options of the legacy form are its first argument.
+        let options_index = usize::from(!is_legacy);
+        let Some(options) = options_object(&expr.args[options_index].expr) else {
+            return false;
+        };
+        if key_value_prop(options, "loadableGenerated").is_some() {
+            return false;
+        }
+
+        // The loader is replaced before looking for its imports, so that
+        // components rendered on the client only don't get `loadableGenerated`.
+        if self.is_server_compiler
+            && matches!(
+                key_value_prop(options, "ssr"),
+                Some(Expr::Lit(Lit::Bool(Bool { value: false, .. })))
+            )
+        {
+            let null_loader = Box::new(quote!("async () => null" as Expr));
+            if let Expr::Object(legacy_options) = &mut *expr.args[0].expr {
+                if let Some(loader) = legacy_loader(legacy_options) {
+                    *loader = null_loader;
+                }
+            } else {
+                expr.args[0].expr = null_loader;
+            }
+            return false;
+        }
+
+        if imports.is_empty() {
+            return false;
+        }
 
-                    let second_arg = ExprOrSpread {
-                        spread: None,
-                        expr: Box::new(Expr::Object(ObjectLit {
+        let generated = if self.is_development || self.is_server_compiler {
+            let prefix = format!(
+                "{} -> ",
+                rel_filename(self.pages_dir.as_deref(), &self.filename)
+            );
+            imports
+                .into_iter()
+                .fold(LoadableGenerated::new(), |generated, import| {
+                    generated.module_id(quote!(
+                        "$left + $right" as Expr,
+                        left: Expr = prefix.clone().into(),
+                        right: Expr = *import,
+                    ))
+                })
+        } else {
+            LoadableGenerated::new().extend(vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(
+                KeyValueProp {
+                    key: PropName::Ident(Ident::new("webpack".into(), DUMMY_SP)),
+                    value: Box::new(quote!(
+                        "() => $ids" as Expr,
+                        ids: Expr = Expr::Array(ArrayLit {
                             span: DUMMY_SP,
-                            props,
-                        })),
-                    };
+                            elems: imports
+                                .into_iter()
+                                .map(|import| {
+                                    Some(
+                                        resolve_module_id(self.config.webpack_resolver, *import)
+                                            .as_arg(),
+                                    )
+                                })
+                                .collect(),
+                        }),
+                    )),
+                },
+            )))])
+        };
 
-                    if expr.args.len() == 2 {
-                        expr.args[1] = second_arg;
-                    } else {
-                        expr.args.push(second_arg)
-                    }
+        if let Some(options) = options_object_mut(&mut expr.args[options_index].expr) {
+            options.props.push(generated.into_prop());
+        }
+
+        // Like before Next.js 7, `dynamic(import('...'))` is turned into
+        // `dynamic(() => import('...'))`.
+        if expr.args[0].expr.is_call() {
+            let loader = expr.args[0].expr.take();
+            expr.args[0].expr = Box::new(quote!("() => $loader" as Expr, loader: Expr = *loader));
+        }
+
+        true
+    }
+
+    fn emit(&mut self, level: DiagnosticLevel, span: Span, message: Message) {
+        self.emit_diagnostic(DynamicDiagnostic::new(level, span, message));
+    }
+
+    fn emit_diagnostic(&mut self, mut diagnostic: DynamicDiagnostic) {
+        if self.config.strict {
+            diagnostic.level = DiagnosticLevel::Error;
+        }
+        diagnostic.filename = Some(rel_filename(self.pages_dir.as_deref(), &self.filename));
+        if diagnostic.specifier.is_none() {
+            diagnostic.specifier = self
+                .dynamically_imported_specifier
+                .as_ref()
+                .map(|(specifier, _)| specifier.clone());
+        }
+        // The diagnostics of `transform_dual` are told apart by backend once the
+        // module is folded.
+        if let Some(webpack_delta) = &mut self.webpack_delta {
+            webpack_delta.diagnostics.push(diagnostic);
+            return;
+        }
+        self.stats.diagnostics_emitted += 1;
+
+        // The `HANDLER` may not be set in the wasm runtime, e.g. in browser
+        // playgrounds.
+        if self.config.collect_diagnostics || self.defers_diagnostics || !HANDLER.is_set() {
+            self.diagnostics.push(diagnostic);
+        } else {
+            diagnostic.emit();
+        }
+    }
+
+    /// Prepares the patcher to transform another module, keeping the buffers
+    /// of the previous one to avoid reallocating them.
+    pub fn reset(&mut self, filename: FileName) {
+        self.filename = filename;
+        self.dynamic_bindings.clear();
+        self.dynamic_namespace_bindings.clear();
+        self.is_compiled_es_module = false;
+        self.is_next_dynamic_first_arg = false;
+        self.has_dynamic_import = false;
+        self.stmt_depth = 0;
+        self.dynamically_imported_specifier = None;
+        self.generated_keys.clear();
+        self.react_use_key = None;
+        self.react_use_idents = None;
+        self.eager_imports.clear();
+        self.is_eager = false;
+        self.inline_component = None;
+        self.inline_create_element = None;
+        self.babel_compat_imports.clear();
+        self.diagnostics.clear();
+        self.stats = Default::default();
+        self.injected_items.imports.clear();
+        self.injected_items.eager_imports.clear();
+        self.injected_items.helper_imports.clear();
+        self.magic_comments.clear();
+        self.declared_bindings.clear();
+        if let Some(webpack_delta) = &mut self.webpack_delta {
+            *webpack_delta = WebpackDelta::new();
+        }
+        if let Some(registered_keys) = &mut self.registered_keys {
+            registered_keys.clear();
+        }
+        match &mut self.state {
+            NextDynamicPatcherState::Webpack => {}
+            NextDynamicPatcherState::Custom { items, .. } => items.clear(),
+            NextDynamicPatcherState::Turbopack { imports, .. } => imports.clear(),
+        }
+    }
+
+    /// Returns the statistics of the transform so far.
+    pub fn stats(&self) -> TransformStats {
+        self.stats
+    }
+
+    /// Returns the module items added to the module, once it's folded.
+    pub fn injected_items(&self) -> &InjectedItems {
+        &self.injected_items
+    }
+
+    /// Adds the `magic_comments` found in the module to `comments`.
+    fn add_magic_comments(&mut self, comments: &impl Comments) {
+        for (pos, text) in self.magic_comments.drain(..) {
+            comments.add_leading(
+                pos,
+                Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: format!(" {text} ").into(),
+                },
+            );
+        }
+    }
+
+    /// Returns the diagnostics collected with `Config::collect_diagnostics`,
+    /// as an error if any of them is an error, or as the list of warnings
+    /// otherwise.
+    pub fn finish(self) -> Result<Vec<DynamicDiagnostic>, Vec<DynamicDiagnostic>> {
+        if self
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+        {
+            Err(self.diagnostics)
+        } else {
+            Ok(self.diagnostics)
+        }
+    }
+
+    /// Turns a transformed `dynamic()` call into a component suspending on the
+    /// promise of its loader.
+    fn react_use_component(&mut self, key: Expr, call: CallExpr) -> Expr {
+        let idents = self
+            .react_use_idents
+            .get_or_insert_with(|| ReactUseIdents {
+                react_use: private_ident!("use"),
+                create_element: private_ident!("createElement"),
+                loader_promise_cache: private_ident!("loaderPromiseCache"),
+            })
+            .clone();
+
+        let type_args = call.type_args;
+        let loader = call
+            .args
+            .into_iter()
+            .next()
+            .map(|arg| arg.expr)
+            .unwrap_or_else(|| Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))));
+
+        let mut component = if self.config.react_compiler {
+            quote!(
+                r#"
+                (props) => {
+                    "use no memo";
+                    return $create_element($react_use($cache.get($key, $loader)), props);
                 }
+                "# as Expr,
+                create_element = idents.create_element,
+                react_use = idents.react_use,
+                cache = idents.loader_promise_cache,
+                key: Expr = key,
+                loader: Expr = *loader,
+            )
+        } else {
+            quote!(
+                "(props) => $create_element($react_use($cache.get($key, $loader)), props)" as Expr,
+                create_element = idents.create_element,
+                react_use = idents.react_use,
+                cache = idents.loader_promise_cache,
+                key: Expr = key,
+                loader: Expr = *loader,
+            )
+        };
+        if self.config.preserve_types {
+            annotate_props(&mut component, type_args.as_deref());
+        }
+        component
+    }
+
+    /// Returns whether the module matches one of the `Config::exclude`
+    /// patterns.
+    fn is_excluded(&self) -> bool {
+        if self.config.exclude.is_empty() {
+            return false;
+        }
+
+        let filename = self.filename.to_string();
+        self.config
+            .exclude
+            .iter()
+            .any(|pattern| paths::matches_pattern(pattern, &filename))
+    }
+
+    /// Returns whether module items need to be added for the `dynamic()`
+    /// calls transformed so far.
+    fn has_module_items_to_add(&self) -> bool {
+        let has_state_items = match &self.state {
+            NextDynamicPatcherState::Webpack => false,
+            NextDynamicPatcherState::Custom { items, .. } => !items.is_empty(),
+            NextDynamicPatcherState::Turbopack { imports, .. } => !imports.is_empty(),
+        };
+
+        has_state_items
+            || self.react_use_idents.is_some()
+            || self.inline_create_element.is_some()
+            || !self.eager_imports.is_empty()
+    }
+
+    /// Replaces the `import()` of an eager specifier with a promise resolving
+    /// to its statically imported namespace.
+    fn eager_import(&mut self, specifier: String) -> CallExpr {
+        self.is_eager = true;
+
+        let namespace = self.eager_namespace(specifier);
+        quote!("Promise.resolve($namespace)" as Expr, namespace = namespace).expect_call()
+    }
+
+    /// Returns the component replacing a `dynamic()` call, which renders the
+    /// default export of `module` synchronously.
+    fn sync_component(
+        &mut self,
+        module: Expr,
+        type_args: Option<&TsTypeParamInstantiation>,
+    ) -> Expr {
+        let create_element = self
+            .inline_create_element
+            .get_or_insert_with(|| private_ident!("createElement"))
+            .clone();
+
+        let mut component = quote!(
+            "(props) => $create_element($module.default, props)" as Expr,
+            create_element = create_element,
+            module: Expr = module,
+        );
+        if self.config.preserve_types {
+            annotate_props(&mut component, type_args);
+        }
+        component
+    }
+
+    /// Returns the ident of the namespace import of `specifier`, which is added
+    /// to the module by `maybe_add_eager_imports`.
+    fn eager_namespace(&mut self, specifier: String) -> Ident {
+        match self
+            .eager_imports
+            .iter()
+            .find(|(eager_specifier, _)| *eager_specifier == specifier)
+        {
+            Some((_, namespace)) => namespace.clone(),
+            None => {
+                let namespace = private_ident!("eagerModule");
+                self.eager_imports.push((specifier, namespace.clone()));
+                namespace
             }
         }
-        expr
     }
-}
 
-fn module_id_options(module_id: Expr) -> Vec<PropOrSpread> {
-    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-        key: PropName::Ident(Ident::new("modules".into(), DUMMY_SP)),
-        value: Box::new(Expr::Array(ArrayLit {
-            elems: vec![Some(ExprOrSpread {
-                expr: Box::new(module_id),
-                spread: None,
-            })],
-            span: DUMMY_SP,
-        })),
-    })))]
-}
-
-fn webpack_options(module_id: Expr) -> Vec<PropOrSpread> {
-    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-        key: PropName::Ident(Ident::new("webpack".into(), DUMMY_SP)),
-        value: Box::new(Expr::Arrow(ArrowExpr {
-            params: vec![],
-            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Array(ArrayLit {
-                elems: vec![Some(ExprOrSpread {
-                    expr: Box::new(module_id),
-                    spread: None,
-                })],
+    fn maybe_add_inline_imports(&mut self, items: &mut Vec<ModuleItem>) {
+        let Some(create_element) = self.inline_create_element.take() else {
+            return;
+        };
+
+        self.injected_items.helper_imports.push("react".into());
+
+        items.push(named_import(
+            vec![("createElement", create_element)],
+            "react",
+        ));
+    }
+
+    fn maybe_add_eager_imports(&mut self, items: &mut Vec<ModuleItem>) {
+        let injected_items = &mut self.injected_items;
+        items.extend(self.eager_imports.drain(..).map(|(specifier, namespace)| {
+            injected_items.eager_imports.push(specifier.clone());
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                 span: DUMMY_SP,
-            })))),
-            is_async: false,
-            is_generator: false,
-            span: DUMMY_SP,
-            return_type: None,
-            type_params: None,
-        })),
-    })))]
-}
+                specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                    span: DUMMY_SP,
+                    local: namespace,
+                })],
+                src: Box::new(specifier.into()),
+                type_only: false,
+                with: None,
+            }))
+        }));
+    }
+
+    fn maybe_add_custom_items(&mut self, items: &mut Vec<ModuleItem>) {
+        let NextDynamicPatcherState::Custom {
+            items: custom_items,
+            ..
+        } = &mut self.state
+        else {
+            return;
+        };
+
+        for item in custom_items.iter() {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                self.injected_items
+                    .helper_imports
+                    .push(import.src.value.to_string());
+            }
+        }
+        items.append(custom_items);
+    }
+
+    fn maybe_add_react_use_imports(&mut self, items: &mut Vec<ModuleItem>) {
+        let Some(idents) = self.react_use_idents.take() else {
+            return;
+        };
+
+        self.injected_items
+            .helper_imports
+            .extend(["react".to_string(), LOADER_PROMISE_CACHE_MODULE.to_string()]);
+
+        items.extend([
+            named_import(
+                vec![
+                    ("use", idents.react_use),
+                    ("createElement", idents.create_element),
+                ],
+                "react",
+            ),
+            named_import(
+                vec![("loaderPromiseCache", idents.loader_promise_cache)],
+                LOADER_PROMISE_CACHE_MODULE,
+            ),
+        ]);
+    }
 
-impl NextDynamicPatcher {
     fn maybe_add_dynamically_imported_specifier(&mut self, items: &mut Vec<ModuleItem>) {
         let NextDynamicPatcherState::Turbopack {
             dynamic_transition_name,
+            key_export,
             imports,
+            ..
         } = &mut self.state
         else {
             return;
         };
+        if imports.is_empty() {
+            return;
+        }
 
-        let mut new_items = Vec::with_capacity(imports.len() * 2);
+        // Turbopack applies a `"TURBOPACK { ... }"` directive to the import
+        // statement directly following it only, so every import needs its own
+        // directive even when they're identical. Calls importing the same
+        // specifier already share a single import, and with it a single
+        // directive.
+        items.reserve(imports.len() * 2);
 
         for import in std::mem::take(imports) {
+            if let Some(webpack_delta) = &mut self.webpack_delta {
+                webpack_delta
+                    .turbopack_imports
+                    .push(import.id_ident().to_id());
+            }
+            self.injected_items.imports.push(InjectedImport {
+                specifier: import.specifier().to_string(),
+                transition: match import {
+                    TurbopackImport::DevelopmentTransition { .. }
+                    | TurbopackImport::BuildTransition { .. } => {
+                        Some(dynamic_transition_name.clone())
+                    }
+                    TurbopackImport::DevelopmentId { .. } | TurbopackImport::BuildId { .. } => None,
+                },
+            });
+
             match import {
                 TurbopackImport::DevelopmentTransition {
                     id_ident,
@@ -464,26 +3009,22 @@ impl NextDynamicPatcher {
                 } => {
                     // The transition should return both the target module's id
                     // and the chunks it needs to run.
-                    new_items.push(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                    let mut specifiers = vec![ImportSpecifier::Default(ImportDefaultSpecifier {
                         span: DUMMY_SP,
-                        expr: Box::new(Expr::Lit(Lit::Str(
-                            format!("TURBOPACK {{ transition: {dynamic_transition_name} }}").into(),
-                        ))),
-                    })));
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        local: id_ident,
+                    })];
+                    if let Some(chunks_ident) = chunks_ident {
+                        specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local: chunks_ident,
+                            imported: Some(Ident::new("chunks".into(), DUMMY_SP).into()),
+                            is_type_only: false,
+                        }));
+                    }
+                    items.push(transition_directive(dynamic_transition_name));
+                    items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                         span: DUMMY_SP,
-                        specifiers: vec![
-                            ImportSpecifier::Default(ImportDefaultSpecifier {
-                                span: DUMMY_SP,
-                                local: id_ident,
-                            }),
-                            ImportSpecifier::Named(ImportNamedSpecifier {
-                                span: DUMMY_SP,
-                                local: chunks_ident,
-                                imported: Some(Ident::new("chunks".into(), DUMMY_SP).into()),
-                                is_type_only: false,
-                            }),
-                        ],
+                        specifiers,
                         src: Box::new(specifier.into()),
                         type_only: false,
                         with: None,
@@ -495,19 +3036,17 @@ impl NextDynamicPatcher {
                 } => {
                     // We don't want this import to cause the imported module to be considered for
                     // chunking through this import; we only need the module id.
-                    new_items.push(quote!(
+                    items.push(quote!(
                         "\"TURBOPACK { chunking-type: none }\";" as ModuleItem
                     ));
                     // Turbopack will automatically transform the imported `__turbopack_module_id__`
                     // identifier into the imported module's id.
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                         span: DUMMY_SP,
                         specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
                             span: DUMMY_SP,
                             local: id_ident,
-                            imported: Some(
-                                Ident::new("__turbopack_module_id__".into(), DUMMY_SP).into(),
-                            ),
+                            imported: Some(export_name("__turbopack_module_id__")),
                             is_type_only: false,
                         })],
                         src: Box::new(specifier.into()),
@@ -521,24 +3060,26 @@ impl NextDynamicPatcher {
                 } => {
                     // The transition should make sure the imported module ends up in the dynamic
                     // manifest.
-                    new_items.push(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
-                        span: DUMMY_SP,
-                        expr: Box::new(Expr::Lit(Lit::Str(
-                            format!("TURBOPACK {{ transition: {dynamic_transition_name} }}").into(),
-                        ))),
-                    })));
-                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
-                    // identifier into the imported module's id.
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    items.push(transition_directive(dynamic_transition_name));
+                    // The asset returned by the transition exports the key of the manifest entry.
+                    items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                         span: DUMMY_SP,
-                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                            span: DUMMY_SP,
-                            local: id_ident,
-                            imported: Some(
-                                Ident::new("__turbopack_module_id__".into(), DUMMY_SP).into(),
-                            ),
-                            is_type_only: false,
-                        })],
+                        specifiers: vec![match key_export {
+                            TurbopackKeyExport::Default => {
+                                ImportSpecifier::Default(ImportDefaultSpecifier {
+                                    span: DUMMY_SP,
+                                    local: id_ident,
+                                })
+                            }
+                            TurbopackKeyExport::Named(name) => {
+                                ImportSpecifier::Named(ImportNamedSpecifier {
+                                    span: DUMMY_SP,
+                                    local: id_ident,
+                                    imported: Some(export_name(name)),
+                                    is_type_only: false,
+                                })
+                            }
+                        }],
                         src: Box::new(specifier.into()),
                         type_only: false,
                         with: None,
@@ -550,19 +3091,17 @@ impl NextDynamicPatcher {
                 } => {
                     // We don't want this import to cause the imported module to be considered for
                     // chunking through this import; we only need the module id.
-                    new_items.push(quote!(
+                    items.push(quote!(
                         "\"TURBOPACK { chunking-type: none }\";" as ModuleItem
                     ));
                     // Turbopack will automatically transform the imported `__turbopack_module_id__`
                     // identifier into the imported module's id.
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                         span: DUMMY_SP,
                         specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
                             span: DUMMY_SP,
                             local: id_ident,
-                            imported: Some(
-                                Ident::new("__turbopack_module_id__".into(), DUMMY_SP).into(),
-                            ),
+                            imported: Some(export_name("__turbopack_module_id__")),
                             is_type_only: false,
                         })],
                         src: Box::new(specifier.into()),
@@ -572,14 +3111,187 @@ impl NextDynamicPatcher {
                 }
             }
         }
+    }
+}
+
+/// Annotates the `props` of a component generated in place of a `dynamic()`
+/// call with the props type the call is given, e.g. `Props` for
+/// `dynamic<Props>(...)`, or `any`.
+fn annotate_props(component: &mut Expr, type_args: Option<&TsTypeParamInstantiation>) {
+    let Expr::Arrow(ArrowExpr { params, .. }) = component else {
+        return;
+    };
+    let Some(Pat::Ident(props)) = params.first_mut() else {
+        return;
+    };
+
+    let type_ann = match type_args.and_then(|type_args| type_args.params.first()) {
+        Some(props_type) => props_type.clone(),
+        None => Box::new(TsType::TsKeywordType(TsKeywordType {
+            span: DUMMY_SP,
+            kind: TsKeywordTypeKind::TsAnyKeyword,
+        })),
+    };
+    props.type_ann = Some(Box::new(TsTypeAnn {
+        span: DUMMY_SP,
+        type_ann,
+    }));
+}
+
+/// Returns the position the injected items are inserted at: after the
+/// directives at the start of the module, like `"use client"`, and the imports
+/// following them.
+fn injection_index(items: &[ModuleItem]) -> usize {
+    let directives = items
+        .iter()
+        .take_while(|item| {
+            matches!(
+                item,
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))
+                    if matches!(&**expr, Expr::Lit(Lit::Str(..)))
+            )
+        })
+        .count();
+
+    // A `"TURBOPACK { ... }"` directive between imports applies to the import
+    // following it, so the items can't be inserted in between.
+    directives
+        + items[directives..]
+            .iter()
+            .take_while(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(..))))
+            .count()
+}
+
+/// Returns the position of the specifier and the name of the export of an
+/// `import('...').then((mod) => mod.Name)` expression.
+fn loader_export(call: &CallExpr) -> Option<(BytePos, String)> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(then),
+        ..
+    }) = &**callee
+    else {
+        return None;
+    };
+    let Expr::Call(CallExpr {
+        callee: Callee::Import(..),
+        args: import_args,
+        ..
+    }) = &**obj
+    else {
+        return None;
+    };
+    let Expr::Lit(Lit::Str(specifier)) = &*import_args.first()?.expr else {
+        return None;
+    };
+    let Expr::Arrow(ArrowExpr { params, body, .. }) = &*call.args.first()?.expr else {
+        return None;
+    };
+    if &*then.sym != "then" || call.args.len() != 1 || params.len() != 1 {
+        return None;
+    }
+
+    match (&params[0], &**body) {
+        (Pat::Ident(module), BlockStmtOrExpr::Expr(body)) => match &**body {
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(export),
+                ..
+            }) if obj
+                .as_ident()
+                .is_some_and(|obj| obj.to_id() == module.id.to_id()) =>
+            {
+                Some((specifier.span.lo, export.sym.to_string()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the specifier of a `() => import('...')` loader.
+fn inline_loader_specifier(loader: &Expr) -> Option<&str> {
+    let Expr::Arrow(ArrowExpr { params, body, .. }) = loader else {
+        return None;
+    };
+    let BlockStmtOrExpr::Expr(body) = &**body else {
+        return None;
+    };
+    match &**body {
+        Expr::Call(CallExpr {
+            callee: Callee::Import(..),
+            args,
+            ..
+        }) if params.is_empty() && args.len() == 1 => match &*args[0].expr {
+            Expr::Lit(Lit::Str(specifier)) => Some(&*specifier.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
 
-        new_items.append(items);
+/// Returns the `"TURBOPACK { transition: ... }"` directive annotating the
+/// import which follows it.
+fn transition_directive(transition_name: &str) -> ModuleItem {
+    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Lit(Lit::Str(
+            format!("TURBOPACK {{ transition: {transition_name} }}").into(),
+        ))),
+    }))
+}
 
-        std::mem::swap(&mut new_items, items)
+/// Returns the name of an imported export. Only the local binding of the
+/// generated imports takes part in hygiene, so the export keeps its exact name
+/// even when the module declares a binding with the same name. Names which
+/// aren't valid identifiers are imported as strings, e.g.
+/// `import { "some-key" as id } from "..."`.
+fn export_name(name: &str) -> ModuleExportName {
+    if Ident::verify_symbol(name).is_ok() {
+        Ident::new(name.into(), DUMMY_SP).into()
+    } else {
+        ModuleExportName::Str(name.into())
     }
 }
 
-fn rel_filename(base: Option<&Path>, file: &FileName) -> String {
+fn named_import(specifiers: Vec<(&str, Ident)>, src: &str) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers: specifiers
+            .into_iter()
+            .map(|(imported, local)| {
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: DUMMY_SP,
+                    local,
+                    imported: Some(Ident::new(imported.into(), DUMMY_SP).into()),
+                    is_type_only: false,
+                })
+            })
+            .collect(),
+        src: Box::new(src.into()),
+        type_only: false,
+        with: None,
+    }))
+}
+
+/// Returns the current time, or `None` in the wasm runtime, where `Instant`
+/// panics.
+#[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+fn now() -> Option<Instant> {
+    Some(Instant::now())
+}
+
+/// Returns the current time, or `None` in the wasm runtime, where `Instant`
+/// panics.
+#[cfg(any(feature = "wasm", target_arch = "wasm32"))]
+fn now() -> Option<Instant> {
+    None
+}
+
+pub(crate) fn rel_filename(base: Option<&Path>, file: &FileName) -> String {
     let base = match base {
         Some(v) => v,
         None => return file.to_string(),
@@ -587,12 +3299,19 @@ fn rel_filename(base: Option<&Path>, file: &FileName) -> String {
 
     let file = match file {
         FileName::Real(v) => v,
+        // Already normalized POSIX paths, e.g. in SWC's wasm plugin runtime.
+        FileName::Custom(v) if v.starts_with('/') => {
+            return base
+                .to_str()
+                .and_then(|base| paths::diff_posix_paths(v, base))
+                .unwrap_or_else(|| v.clone());
+        }
         _ => {
             return file.to_string();
         }
     };
 
-    let rel_path = diff_paths(file, base);
+    let rel_path = paths::diff_paths(file, base);
 
     let rel_path = match rel_path {
         Some(v) => v,
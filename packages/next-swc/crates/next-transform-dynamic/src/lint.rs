@@ -0,0 +1,190 @@
+//! Implements the most common `next lint` rules, so that their warnings can be
+//! reported while compiling instead of waiting for ESLint.
+
+use std::path::Path;
+
+use swc_core::{
+    common::FileName,
+    ecma::{
+        ast::{
+            JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXElementName, JSXOpeningElement, Lit,
+            Module,
+        },
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+use crate::{messages::Message, rel_filename, DiagnosticLevel, DynamicDiagnostic};
+
+/// Returns a warning for each `<img>` element, `<a>` element linking to a page
+/// and synchronous `<script>` element in `module`, the module `filename`,
+/// whose diagnostics are relative to `pages_dir`.
+///
+/// Like the ESLint rule, links are only reported when they match one of the
+/// pages found in `pages_dir`. When they can't be listed, e.g. in SWC's wasm
+/// plugin runtime, which can't access the filesystem, any internal link is
+/// reported, except for the API routes, the `/_next` assets and the other
+/// files, whose path has an extension.
+pub fn lint(
+    module: &Module,
+    filename: &FileName,
+    pages_dir: Option<&Path>,
+) -> Vec<DynamicDiagnostic> {
+    let mut linter = Linter {
+        pages: pages_dir.and_then(pages),
+        diagnostics: vec![],
+    };
+    module.visit_with(&mut linter);
+
+    let filename = rel_filename(pages_dir, filename);
+    for diagnostic in &mut linter.diagnostics {
+        diagnostic.filename = Some(filename.clone());
+    }
+    linter.diagnostics
+}
+
+struct Linter {
+    /// The routes of the pages, as their path segments.
+    pages: Option<Vec<Vec<String>>>,
+    diagnostics: Vec<DynamicDiagnostic>,
+}
+
+impl Linter {
+    /// Returns whether `href` links to a page, which must be navigated to
+    /// with `next/link`.
+    fn is_page_link(&self, href: &str) -> bool {
+        if !href.starts_with('/') || href.starts_with("//") {
+            return false;
+        }
+        let path = href.split(['?', '#']).next().unwrap_or_default();
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        match &self.pages {
+            Some(pages) => pages.iter().any(|page| matches_route(page, &segments)),
+            None => {
+                !matches!(segments.first(), Some(&"api" | &"_next"))
+                    && !segments.last().is_some_and(|segment| segment.contains('.'))
+            }
+        }
+    }
+}
+
+/// Returns the routes of the pages in `pages_dir`, or `None` when the
+/// directory can't be read or contains no page.
+#[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+fn pages(pages_dir: &Path) -> Option<Vec<Vec<String>>> {
+    /// The extensions of the pages, the default `pageExtensions` of Next.js.
+    const PAGE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js"];
+
+    fn visit(dir: &Path, route: &[String], pages: &mut Vec<Vec<String>>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if path.is_dir() {
+                // The API routes aren't pages.
+                if !(route.is_empty() && name == "api") {
+                    let mut route = route.to_vec();
+                    route.push(name.to_string());
+                    visit(&path, &route, pages);
+                }
+                continue;
+            }
+
+            let Some((stem, extension)) = name.rsplit_once('.') else {
+                continue;
+            };
+            // `_app`, `_document` and `_error` aren't routes.
+            if !PAGE_EXTENSIONS.contains(&extension) || stem.starts_with('_') {
+                continue;
+            }
+            let mut route = route.to_vec();
+            if stem != "index" {
+                route.push(stem.to_string());
+            }
+            pages.push(route);
+        }
+    }
+
+    let mut pages = vec![];
+    visit(pages_dir, &[], &mut pages);
+    (!pages.is_empty()).then_some(pages)
+}
+
+#[cfg(any(feature = "wasm", target_arch = "wasm32"))]
+fn pages(_pages_dir: &Path) -> Option<Vec<Vec<String>>> {
+    None
+}
+
+/// Returns whether the path `segments` match the `route` of a page, whose
+/// segments can be dynamic (`[slug]`), catch-all (`[...slug]`) or optional
+/// catch-all (`[[...slug]]`).
+fn matches_route(route: &[String], segments: &[&str]) -> bool {
+    match route.split_first() {
+        None => segments.is_empty(),
+        Some((segment, _)) if segment.starts_with("[[...") => true,
+        Some((segment, _)) if segment.starts_with("[...") => !segments.is_empty(),
+        Some((segment, route)) => match segments.split_first() {
+            Some((first, segments)) => {
+                (segment.starts_with('[') || segment == *first) && matches_route(route, segments)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Returns the attribute named `name` of `element`, with `None` as its value
+/// for attributes without one or with a non-string value.
+fn attr<'a>(element: &'a JSXOpeningElement, name: &str) -> Option<Option<&'a str>> {
+    element.attrs.iter().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) => match &attr.name {
+            JSXAttrName::Ident(ident) if &*ident.sym == name => Some(match &attr.value {
+                Some(JSXAttrValue::Lit(Lit::Str(value))) => Some(&*value.value),
+                _ => None,
+            }),
+            _ => None,
+        },
+        JSXAttrOrSpread::SpreadElement(..) => None,
+    })
+}
+
+impl Visit for Linter {
+    noop_visit_type!();
+
+    fn visit_jsx_opening_element(&mut self, element: &JSXOpeningElement) {
+        element.visit_children_with(self);
+
+        let JSXElementName::Ident(name) = &element.name else {
+            return;
+        };
+
+        let message = match &*name.sym {
+            "img" => Message::NoImgElement,
+            "a" => match attr(element, "href") {
+                Some(Some(href)) if self.is_page_link(href) => Message::NoHtmlLinkForPages { href },
+                _ => return,
+            },
+            "script"
+                if attr(element, "src").is_some()
+                    && attr(element, "async").is_none()
+                    && attr(element, "defer").is_none() =>
+            {
+                Message::NoSyncScripts
+            }
+            _ => return,
+        };
+
+        self.diagnostics.push(DynamicDiagnostic::new(
+            DiagnosticLevel::Warning,
+            element.span,
+            message,
+        ));
+    }
+}
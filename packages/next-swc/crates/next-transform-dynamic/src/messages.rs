@@ -0,0 +1,278 @@
+//! The messages of the diagnostics reported by the crate, along with their
+//! stable codes. Tools like the dev overlay, telemetry or the docs should match
+//! on the codes, so that the messages can be improved without breaking them.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The stable code of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    ScriptNeedsImports,
+    NestedCall,
+    InvokedComponent,
+    EdgeEntry,
+    NoArguments,
+    TooManyArguments,
+    InvalidOptionsType,
+    NoImportInLoader,
+    KeyCollision,
+    KeyRegistryCollision,
+    UnknownOption,
+    DuplicateOption,
+    LoadingElement,
+    ComputedOption,
+    NamedImport,
+    SsrBeforeSpread,
+    ConditionalImport,
+    EdgeRuntimeApi,
+    NoImgElement,
+    NoHtmlLinkForPages,
+    NoSyncScripts,
+}
+
+impl DiagnosticCode {
+    /// Returns the code, e.g. `next-dynamic/no-arguments`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCode::ScriptNeedsImports => "next-dynamic/script-needs-imports",
+            DiagnosticCode::NestedCall => "next-dynamic/nested-call",
+            DiagnosticCode::InvokedComponent => "next-dynamic/invoked-component",
+            DiagnosticCode::EdgeEntry => "next-dynamic/edge-entry",
+            DiagnosticCode::NoArguments => "next-dynamic/no-arguments",
+            DiagnosticCode::TooManyArguments => "next-dynamic/too-many-arguments",
+            DiagnosticCode::InvalidOptionsType => "next-dynamic/invalid-options-type",
+            DiagnosticCode::NoImportInLoader => "next-dynamic/no-import-in-loader",
+            DiagnosticCode::KeyCollision => "next-dynamic/key-collision",
+            DiagnosticCode::KeyRegistryCollision => "next-dynamic/key-registry-collision",
+            DiagnosticCode::UnknownOption => "next-dynamic/unknown-option",
+            DiagnosticCode::DuplicateOption => "next-dynamic/duplicate-option",
+            DiagnosticCode::LoadingElement => "next-dynamic/loading-element",
+            DiagnosticCode::ComputedOption => "next-dynamic/computed-option",
+            DiagnosticCode::NamedImport => "next-dynamic/named-import",
+            DiagnosticCode::SsrBeforeSpread => "next-dynamic/ssr-before-spread",
+            DiagnosticCode::ConditionalImport => "next-dynamic/conditional-import",
+            DiagnosticCode::EdgeRuntimeApi => "edge-runtime/unsupported-api",
+            DiagnosticCode::NoImgElement => "lint/no-img-element",
+            DiagnosticCode::NoHtmlLinkForPages => "lint/no-html-link-for-pages",
+            DiagnosticCode::NoSyncScripts => "lint/no-sync-scripts",
+        }
+    }
+}
+
+impl DiagnosticCode {
+    /// Returns the page of the docs explaining the diagnostic, if any.
+    pub fn docs_url(self) -> Option<&'static str> {
+        match self {
+            DiagnosticCode::InvokedComponent => Some(
+                "https://nextjs.org/docs/pages/building-your-application/optimizing/lazy-loading",
+            ),
+            DiagnosticCode::InvalidOptionsType => {
+                Some("https://nextjs.org/docs/messages/invalid-dynamic-options-type")
+            }
+            DiagnosticCode::EdgeRuntimeApi => {
+                Some("https://nextjs.org/docs/api-reference/edge-runtime")
+            }
+            DiagnosticCode::NoImgElement => Some("https://nextjs.org/docs/messages/no-img-element"),
+            DiagnosticCode::NoHtmlLinkForPages => {
+                Some("https://nextjs.org/docs/messages/no-html-link-for-pages")
+            }
+            DiagnosticCode::NoSyncScripts => {
+                Some("https://nextjs.org/docs/messages/no-sync-scripts")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A diagnostic message, along with the values it's formatted with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Message<'a> {
+    ScriptNeedsImports,
+    NestedCall,
+    InvokedComponent,
+    EdgeEntry,
+    NoArguments,
+    TooManyArguments,
+    InvalidOptionsType,
+    NoImportInLoader,
+    KeyCollision {
+        key: &'a str,
+        existing_key: &'a str,
+        existing_module_id: &'a str,
+    },
+    KeyRegistryCollision {
+        key: &'a str,
+        existing_key: &'a str,
+        existing_filename: &'a str,
+    },
+    UnknownOption {
+        key: &'a str,
+        suggestion: Option<&'a str>,
+    },
+    DuplicateOption {
+        key: &'a str,
+    },
+    LoadingElement,
+    ComputedOption,
+    NamedImport {
+        name: &'a str,
+    },
+    SsrBeforeSpread,
+    ConditionalImport,
+    /// `api` is e.g. `The Buffer global`.
+    EdgeRuntimeApi {
+        api: &'a str,
+    },
+    NoImgElement,
+    NoHtmlLinkForPages {
+        href: &'a str,
+    },
+    NoSyncScripts,
+}
+
+impl Message<'_> {
+    pub(crate) fn code(&self) -> DiagnosticCode {
+        match self {
+            Message::ScriptNeedsImports => DiagnosticCode::ScriptNeedsImports,
+            Message::NestedCall => DiagnosticCode::NestedCall,
+            Message::InvokedComponent => DiagnosticCode::InvokedComponent,
+            Message::EdgeEntry => DiagnosticCode::EdgeEntry,
+            Message::NoArguments => DiagnosticCode::NoArguments,
+            Message::TooManyArguments => DiagnosticCode::TooManyArguments,
+            Message::InvalidOptionsType => DiagnosticCode::InvalidOptionsType,
+            Message::NoImportInLoader => DiagnosticCode::NoImportInLoader,
+            Message::KeyCollision { .. } => DiagnosticCode::KeyCollision,
+            Message::KeyRegistryCollision { .. } => DiagnosticCode::KeyRegistryCollision,
+            Message::UnknownOption { .. } => DiagnosticCode::UnknownOption,
+            Message::DuplicateOption { .. } => DiagnosticCode::DuplicateOption,
+            Message::LoadingElement => DiagnosticCode::LoadingElement,
+            Message::ComputedOption => DiagnosticCode::ComputedOption,
+            Message::NamedImport { .. } => DiagnosticCode::NamedImport,
+            Message::SsrBeforeSpread => DiagnosticCode::SsrBeforeSpread,
+            Message::ConditionalImport => DiagnosticCode::ConditionalImport,
+            Message::EdgeRuntimeApi { .. } => DiagnosticCode::EdgeRuntimeApi,
+            Message::NoImgElement => DiagnosticCode::NoImgElement,
+            Message::NoHtmlLinkForPages { .. } => DiagnosticCode::NoHtmlLinkForPages,
+            Message::NoSyncScripts => DiagnosticCode::NoSyncScripts,
+        }
+    }
+}
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::ScriptNeedsImports => f.write_str(
+                "next/dynamic can't add the imports it needs to a CommonJS script in this mode, \
+                 convert it to an ES module",
+            ),
+            Message::NestedCall => f.write_str(
+                "next/dynamic calls must be at the top level of the module, not inside the loader \
+                 of another `dynamic()` call",
+            ),
+            Message::InvokedComponent => f.write_str(
+                "The component created by next/dynamic is called as a function, which breaks \
+                 lazy loading. Render it as an element instead, e.g. `<DynamicComponent />`.\nRead \
+                 more: https://nextjs.org/docs/pages/building-your-application/optimizing/lazy-loading",
+            ),
+            Message::EdgeEntry => f.write_str(
+                "next/dynamic is not supported in middleware and edge API routes, as they don't \
+                 render React components",
+            ),
+            Message::NoArguments => f.write_str("next/dynamic requires at least one argument"),
+            Message::TooManyArguments => f.write_str("next/dynamic only accepts 2 arguments"),
+            Message::InvalidOptionsType => f.write_str(
+                "next/dynamic options must be an object literal.\nRead more: \
+                 https://nextjs.org/docs/messages/invalid-dynamic-options-type",
+            ),
+            Message::NoImportInLoader => f.write_str(
+                "next/dynamic requires the loader to contain an `import()` expression, e.g. \
+                 `dynamic(() => import('./component'))`",
+            ),
+            Message::KeyCollision {
+                key,
+                existing_key,
+                existing_module_id,
+            } => write!(
+                f,
+                "next/dynamic key \"{key}\" collides with the key \"{existing_key}\" generated \
+                 for \"{existing_module_id}\" in the same module"
+            ),
+            Message::KeyRegistryCollision {
+                key,
+                existing_key,
+                existing_filename,
+            } => write!(
+                f,
+                "next/dynamic key \"{key}\" collides with the key \"{existing_key}\" generated by \
+                 {existing_filename}"
+            ),
+            Message::UnknownOption {
+                key,
+                suggestion: Some(suggestion),
+            } => write!(
+                f,
+                "Unknown next/dynamic option \"{key}\", did you mean \"{suggestion}\"?"
+            ),
+            Message::UnknownOption {
+                key,
+                suggestion: None,
+            } => write!(f, "Unknown next/dynamic option \"{key}\""),
+            Message::DuplicateOption { key } => write!(
+                f,
+                "next/dynamic option \"{key}\" is set more than once, only the last value is used"
+            ),
+            Message::LoadingElement => f.write_str(
+                "next/dynamic `loading` option must be a component, not an element, e.g. \
+                 `loading: () => <Spinner />` instead of `loading: <Spinner />`",
+            ),
+            Message::ComputedOption => f.write_str(
+                "next/dynamic options with computed keys can't be read at compile time, e.g. \
+                 `ssr: false` won't be applied to the server bundle. Use a plain key instead.",
+            ),
+            Message::NamedImport { name } => write!(
+                f,
+                "`{name}` isn't exported by next/dynamic. Import `dynamic` with `import dynamic \
+                 from 'next/dynamic'`."
+            ),
+            Message::SsrBeforeSpread => f.write_str(
+                "next/dynamic option \"ssr\" is set before spread options, which can override it at \
+                 runtime. Set it after the spread instead.",
+            ),
+            Message::ConditionalImport => f.write_str(
+                "next/dynamic must be imported at the top level of the module, otherwise the \
+                 transform can't reliably find its calls, and their components may not be split \
+                 into separate chunks. Use `import dynamic from 'next/dynamic'` instead.",
+            ),
+            Message::EdgeRuntimeApi { api } => write!(
+                f,
+                "{api} is not supported in the Edge Runtime.\nRead more: \
+                 https://nextjs.org/docs/api-reference/edge-runtime"
+            ),
+            Message::NoImgElement => f.write_str(
+                "Using `<img>` could result in slower LCP and higher bandwidth. Consider using \
+                 `<Image />` from `next/image` to automatically optimize images.\nRead more: \
+                 https://nextjs.org/docs/messages/no-img-element",
+            ),
+            Message::NoHtmlLinkForPages { href } => write!(
+                f,
+                "Do not use an `<a>` element to navigate to `{href}`. Use `<Link />` from \
+                 `next/link` instead.\nRead more: \
+                 https://nextjs.org/docs/messages/no-html-link-for-pages"
+            ),
+            Message::NoSyncScripts => f.write_str(
+                "Synchronous scripts should not be used.\nRead more: \
+                 https://nextjs.org/docs/messages/no-sync-scripts",
+            ),
+        }
+    }
+}
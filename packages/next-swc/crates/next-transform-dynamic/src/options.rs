@@ -0,0 +1,179 @@
+//! Validates the options object of `dynamic()` calls, whose mistakes are
+//! otherwise silently ignored by the runtime.
+
+use std::collections::HashMap;
+
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::ast::{Expr, ParenExpr, Prop, PropName, PropOrSpread},
+};
+
+use crate::{messages::Message, static_prop_name, DiagnosticLevel, DynamicDiagnostic};
+
+/// The options understood by the runtime of `next/dynamic` or by the
+/// transform.
+const KNOWN_OPTIONS: &[&str] = &[
+    "delay",
+    "loadableGenerated",
+    "loader",
+    "loading",
+    "modules",
+    "prefetch",
+    "preload",
+    "ssr",
+    "suspense",
+    "timeout",
+    "webpack",
+];
+
+/// Returns a warning for each unknown option of `props`, suggesting the known
+/// option it's most likely a typo of, for options set more than once, for a
+/// `loading` option set to an element instead of a component, and for an `ssr`
+/// option followed by spread options. The options are left as is.
+pub(crate) fn check_options(props: &[PropOrSpread]) -> Vec<DynamicDiagnostic> {
+    let mut diagnostics = vec![];
+    let mut first_spans: HashMap<&str, Span> = HashMap::new();
+    let mut has_ssr = false;
+
+    for prop in props {
+        if let PropOrSpread::Spread(..) = prop {
+            if std::mem::take(&mut has_ssr) {
+                diagnostics.push(DynamicDiagnostic::new(
+                    DiagnosticLevel::Warning,
+                    prop.span(),
+                    Message::SsrBeforeSpread,
+                ));
+            }
+            continue;
+        }
+
+        if let Some(key) = prop_name(prop) {
+            if matches!(key, PropName::Computed(..)) && static_prop_name(key).is_none() {
+                diagnostics.push(DynamicDiagnostic::new(
+                    DiagnosticLevel::Warning,
+                    key.span(),
+                    Message::ComputedOption,
+                ));
+                continue;
+            }
+        }
+
+        let Some((key, span)) = prop_key(prop) else {
+            continue;
+        };
+
+        // Like in any object literal, the last value of an option wins.
+        if let Some(first_span) = first_spans.get(key) {
+            let mut diagnostic = DynamicDiagnostic::new(
+                DiagnosticLevel::Warning,
+                span,
+                Message::DuplicateOption { key },
+            );
+            diagnostic
+                .labels
+                .push((*first_span, "first set here".into()));
+            diagnostics.push(diagnostic);
+        } else {
+            first_spans.insert(key, span);
+        }
+
+        if !KNOWN_OPTIONS.contains(&key) {
+            diagnostics.push(DynamicDiagnostic::new(
+                DiagnosticLevel::Warning,
+                span,
+                Message::UnknownOption {
+                    key,
+                    suggestion: did_you_mean(key),
+                },
+            ));
+        }
+
+        if key == "ssr" {
+            has_ssr = true;
+        }
+
+        if key == "loading" {
+            if let Some(value) = prop_value(prop).filter(|value| is_element(value)) {
+                diagnostics.push(DynamicDiagnostic::new(
+                    DiagnosticLevel::Warning,
+                    value.span(),
+                    Message::LoadingElement,
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Returns the value of a `key: value` property.
+fn prop_value(prop: &PropOrSpread) -> Option<&Expr> {
+    match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(prop) => Some(&prop.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_element(expr: &Expr) -> bool {
+    match expr {
+        Expr::JSXElement(..) | Expr::JSXFragment(..) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_element(expr),
+        _ => false,
+    }
+}
+
+/// Returns the name and the span of the key of `prop`, when it's known at
+/// compile time.
+fn prop_key(prop: &PropOrSpread) -> Option<(&str, Span)> {
+    if let PropOrSpread::Prop(prop) = prop {
+        if let Prop::Shorthand(ident) = &**prop {
+            return Some((&ident.sym, ident.span));
+        }
+    }
+    let key = prop_name(prop)?;
+    Some((static_prop_name(key)?, key.span()))
+}
+
+/// Returns the key of `prop`, unless it's a shorthand or spread property.
+fn prop_name(prop: &PropOrSpread) -> Option<&PropName> {
+    let PropOrSpread::Prop(prop) = prop else {
+        return None;
+    };
+    match &**prop {
+        Prop::KeyValue(prop) => Some(&prop.key),
+        Prop::Method(prop) => Some(&prop.key),
+        Prop::Getter(prop) => Some(&prop.key),
+        Prop::Setter(prop) => Some(&prop.key),
+        Prop::Shorthand(..) | Prop::Assign(..) => None,
+    }
+}
+
+/// Returns the known option closest to `key`, when it's within two edits.
+fn did_you_mean(key: &str) -> Option<&'static str> {
+    KNOWN_OPTIONS
+        .iter()
+        .map(|option| (levenshtein(key, option), *option))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, option)| option)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a != *b);
+            previous = distances[j + 1];
+            distances[j + 1] = substitution.min(distances[j] + 1).min(previous + 1);
+        }
+    }
+
+    distances[b.len()]
+}
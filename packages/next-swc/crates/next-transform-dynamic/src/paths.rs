@@ -0,0 +1,164 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Expands `specifier` using `jsc.paths`-style aliases, where each pattern
+/// (e.g. `@/*`) maps to a list of targets (e.g. `["./src/*"]`). Only the first
+/// target of a pattern is used.
+///
+/// Like TypeScript, an exact pattern match takes precedence over wildcard
+/// patterns, and the wildcard pattern with the longest prefix wins. Returns
+/// `None` when no pattern matches.
+pub(crate) fn expand_path_alias(
+    paths: &[(String, Vec<String>)],
+    specifier: &str,
+) -> Option<String> {
+    let mut best_match: Option<(&str, &str)> = None;
+    let mut best_prefix_len = 0;
+
+    for (pattern, targets) in paths {
+        let Some(target) = targets.first() else {
+            continue;
+        };
+
+        match pattern.split_once('*') {
+            None => {
+                if pattern == specifier {
+                    return Some(target.clone());
+                }
+            }
+            Some((prefix, suffix)) => {
+                if specifier.len() >= prefix.len() + suffix.len()
+                    && specifier.starts_with(prefix)
+                    && specifier.ends_with(suffix)
+                    && (best_match.is_none() || prefix.len() > best_prefix_len)
+                {
+                    best_match = Some((
+                        target.as_str(),
+                        &specifier[prefix.len()..specifier.len() - suffix.len()],
+                    ));
+                    best_prefix_len = prefix.len();
+                }
+            }
+        }
+    }
+
+    best_match.map(|(target, matched)| target.replacen('*', matched, 1))
+}
+
+/// Returns whether `specifier` matches `pattern`, which may contain a `*`
+/// wildcard like the patterns of `jsc.paths`.
+pub(crate) fn matches_pattern(pattern: &str, specifier: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == specifier,
+        Some((prefix, suffix)) => {
+            specifier.len() >= prefix.len() + suffix.len()
+                && specifier.starts_with(prefix)
+                && specifier.ends_with(suffix)
+        }
+    }
+}
+
+/// Resolves `target`, a `jsc.paths` target relative to `base_url`, to a
+/// specifier relative to the directory of `filename`, e.g. `../components/a`.
+/// Returns `None` for targets which aren't relative paths.
+pub(crate) fn relative_specifier(base_url: &Path, target: &str, filename: &Path) -> Option<String> {
+    if !target.starts_with("./") && !target.starts_with("../") {
+        return None;
+    }
+
+    let path = normalize(&base_url.join(target));
+    let relative = diff_paths(path, normalize(filename.parent()?))?;
+    let relative = relative.to_str()?.replace('\\', "/");
+
+    Some(if relative.starts_with("../") {
+        relative
+    } else {
+        format!("./{relative}")
+    })
+}
+
+/// Resolves `specifier`, relative to the directory of `filename`, to a POSIX
+/// path relative to `root`, e.g. `src/components/a`. Returns `None` for
+/// specifiers which aren't relative paths.
+pub(crate) fn root_relative_id(root: &Path, specifier: &str, filename: &Path) -> Option<String> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+
+    let path = normalize(&filename.parent()?.join(specifier));
+    let relative = diff_paths(path, normalize(root))?;
+    Some(relative.to_str()?.replace('\\', "/"))
+}
+
+/// Removes the `.` and `..` components of `path`, without accessing the
+/// filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Returns `path` relative to `base`, or `path` itself when it's absolute and
+/// `base` isn't.
+#[cfg(all(
+    feature = "pathdiff",
+    not(any(feature = "wasm", target_arch = "wasm32"))
+))]
+pub(crate) fn diff_paths(path: impl AsRef<Path>, base: impl AsRef<Path>) -> Option<PathBuf> {
+    pathdiff::diff_paths(path, base)
+}
+
+/// Returns `path` relative to `base`, or `path` itself when it's absolute and
+/// `base` isn't.
+#[cfg(not(all(
+    feature = "pathdiff",
+    not(any(feature = "wasm", target_arch = "wasm32"))
+)))]
+pub(crate) fn diff_paths(path: impl AsRef<Path>, base: impl AsRef<Path>) -> Option<PathBuf> {
+    diff_posix_paths(path.as_ref().to_str()?, base.as_ref().to_str()?).map(PathBuf::from)
+}
+
+/// Like [`diff_paths`], for POSIX paths represented as strings, e.g. the
+/// normalized filenames passed by SWC's wasm plugin runtime.
+pub(crate) fn diff_posix_paths(path: &str, base: &str) -> Option<String> {
+    match (path.starts_with('/'), base.starts_with('/')) {
+        (true, false) => return Some(path.to_string()),
+        (false, true) => return None,
+        _ => {}
+    }
+
+    let path = posix_components(path);
+    let base = posix_components(base);
+    let common_len = path
+        .iter()
+        .zip(&base)
+        .take_while(|(path, base)| path == base)
+        .count();
+
+    let mut relative: Vec<&str> = vec![".."; base.len() - common_len];
+    relative.extend(&path[common_len..]);
+    Some(relative.join("/"))
+}
+
+/// Returns the components of a POSIX path, without its `.` and `..`
+/// components.
+fn posix_components(path: &str) -> Vec<&str> {
+    let mut components = vec![];
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    components
+}
@@ -0,0 +1,107 @@
+//! Aggregates the `dynamic()` imports reachable from each route, so that
+//! per-route prefetch manifests and bundle-size reports can be generated from
+//! the per-module metadata of the transform.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+/// Collects modules and routes, and builds the [`DynamicEntryGraph`].
+///
+/// Modules are identified by caller-provided ids (e.g. resolved file paths),
+/// which must be consistent between static and dynamic edges.
+#[derive(Debug, Default)]
+pub struct DynamicEntryGraphBuilder {
+    modules: HashMap<String, ModuleNode>,
+    routes: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct ModuleNode {
+    imports: Vec<String>,
+    dynamic_imports: Vec<String>,
+}
+
+/// The modules dynamically imported (through `dynamic()`) by a route.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteDynamicEntries {
+    /// Dynamically imported modules, including the ones only reachable
+    /// through other dynamically imported modules.
+    pub dynamic_modules: BTreeSet<String>,
+    /// Modules containing at least one `dynamic()` call.
+    pub importers: BTreeSet<String>,
+}
+
+/// The dynamic entries of every route, keyed by route.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct DynamicEntryGraph {
+    pub routes: BTreeMap<String, RouteDynamicEntries>,
+}
+
+impl DynamicEntryGraphBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a module, along with the modules it statically imports and the
+    /// modules it imports through `dynamic()`.
+    pub fn add_module(
+        &mut self,
+        id: impl Into<String>,
+        imports: Vec<String>,
+        dynamic_imports: Vec<String>,
+    ) -> &mut Self {
+        let module = self.modules.entry(id.into()).or_default();
+        module.imports.extend(imports);
+        module.dynamic_imports.extend(dynamic_imports);
+        self
+    }
+
+    /// Adds a route, along with its entry modules (e.g. the page and its
+    /// layouts).
+    pub fn add_route(&mut self, route: impl Into<String>, entries: Vec<String>) -> &mut Self {
+        self.routes.entry(route.into()).or_default().extend(entries);
+        self
+    }
+
+    pub fn build(&self) -> DynamicEntryGraph {
+        DynamicEntryGraph {
+            routes: self
+                .routes
+                .iter()
+                .map(|(route, entries)| (route.clone(), self.route_entries(entries)))
+                .collect(),
+        }
+    }
+
+    fn route_entries(&self, entries: &[String]) -> RouteDynamicEntries {
+        let mut route_entries = RouteDynamicEntries::default();
+        let mut visited = BTreeSet::new();
+        let mut queue = entries.to_vec();
+
+        while let Some(id) = queue.pop() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+
+            // Modules without metadata (e.g. external packages) are leaves.
+            let Some(module) = self.modules.get(&id) else {
+                continue;
+            };
+
+            if !module.dynamic_imports.is_empty() {
+                route_entries.importers.insert(id);
+            }
+
+            for dynamic_import in &module.dynamic_imports {
+                route_entries.dynamic_modules.insert(dynamic_import.clone());
+                queue.push(dynamic_import.clone());
+            }
+            queue.extend(module.imports.iter().cloned());
+        }
+
+        route_entries
+    }
+}
@@ -0,0 +1,104 @@
+//! Builds the "dynamic imports per route" report printed by `next build`, from
+//! the `dynamic()` calls collected with `Config::dynamic_calls` and the
+//! [`DynamicEntryGraph`], so that Webpack and Turbopack builds print the same
+//! report.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::{route_graph::DynamicEntryGraph, DynamicCall};
+
+/// A module dynamically imported by a route.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteDynamicImport {
+    /// The id of the imported module in the [`DynamicEntryGraph`].
+    pub module: String,
+    /// Whether the module is rendered on the server. A module imported both
+    /// with and without `ssr: false` is reported as rendered on the server.
+    pub ssr: bool,
+    /// The size of the module, when the caller provided one.
+    pub estimated_size: Option<u64>,
+}
+
+/// The dynamic imports of a route.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteSplitReport {
+    /// The dynamically imported modules, sorted by id.
+    pub dynamic_imports: Vec<RouteDynamicImport>,
+    /// The sum of the known sizes of the dynamically imported modules.
+    pub total_estimated_size: u64,
+}
+
+/// The dynamic imports of every route, keyed by route.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SplitReport {
+    pub routes: BTreeMap<String, RouteSplitReport>,
+}
+
+/// Builds the report of `graph`.
+///
+/// The module ids of `graph` must be the files of the `calls` (i.e. the
+/// filenames passed to the transform). `resolve` maps each call to the id of
+/// the module it imports, and `sizes` maps module ids to their estimated size,
+/// e.g. the size of their chunks. Calls which `resolve` returns `None` for are
+/// left out of the report.
+pub fn split_report(
+    graph: &DynamicEntryGraph,
+    calls: &[DynamicCall],
+    resolve: impl Fn(&DynamicCall) -> Option<String>,
+    sizes: &HashMap<String, u64>,
+) -> SplitReport {
+    // Maps the id of each dynamically imported module to whether it's rendered
+    // on the server, per importer.
+    let mut imports_by_importer: HashMap<&str, BTreeMap<String, bool>> = HashMap::new();
+    for call in calls {
+        let Some(module) = resolve(call) else {
+            continue;
+        };
+        *imports_by_importer
+            .entry(call.file.as_str())
+            .or_default()
+            .entry(module)
+            .or_default() |= call.ssr;
+    }
+
+    SplitReport {
+        routes: graph
+            .routes
+            .iter()
+            .map(|(route, entries)| {
+                let mut imports: BTreeMap<&str, bool> = BTreeMap::new();
+                for importer in &entries.importers {
+                    for (module, ssr) in imports_by_importer.get(&**importer).into_iter().flatten()
+                    {
+                        *imports.entry(module.as_str()).or_default() |= *ssr;
+                    }
+                }
+                (route.clone(), route_report(imports, sizes))
+            })
+            .collect(),
+    }
+}
+
+fn route_report(imports: BTreeMap<&str, bool>, sizes: &HashMap<String, u64>) -> RouteSplitReport {
+    let dynamic_imports: Vec<_> = imports
+        .into_iter()
+        .map(|(module, ssr)| RouteDynamicImport {
+            module: module.to_string(),
+            ssr,
+            estimated_size: sizes.get(module).copied(),
+        })
+        .collect();
+
+    RouteSplitReport {
+        total_estimated_size: dynamic_imports
+            .iter()
+            .filter_map(|import| import.estimated_size)
+            .sum(),
+        dynamic_imports,
+    }
+}
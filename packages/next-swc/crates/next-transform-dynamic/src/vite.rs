@@ -0,0 +1,156 @@
+//! A [`DynamicCodegen`] for frameworks bundling with Vite or Rollup, to be used
+//! with [`NextDynamicMode::Custom`](crate::NextDynamicMode::Custom).
+
+use std::path::{Path, PathBuf};
+
+use swc_core::{
+    common::{FileName, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrowExpr, BlockStmtOrExpr, Expr, Ident, KeyValueProp, MemberExpr, MemberProp,
+            MetaPropExpr, MetaPropKind, Prop, PropName, PropOrSpread,
+        },
+        utils::ExprFactory,
+    },
+};
+
+use crate::{
+    codegen::module_id_options, paths, DynamicCallContext, DynamicCodegen, DynamicCodegenOutput,
+};
+
+/// Generates the `loadableGenerated` option of `dynamic()` calls for Vite or
+/// Rollup builds:
+///
+/// - `modules` contains the id of the dynamically imported module, i.e. its
+///   path relative to the root of the project, e.g. `src/components/hello.tsx`,
+///   like the keys of the Vite manifest. Bare specifiers, e.g. of packages, are
+///   used as is.
+/// - `vite` is a function returning the paths of the module as resolved by
+///   Vite, with `import.meta.glob`, e.g. to find its chunks in the Vite
+///   manifest. It's only generated for relative specifiers, the only ones
+///   `import.meta.glob` accepts.
+///
+/// Relative specifiers without an extension are resolved to the file they
+/// import, trying the [`RESOLVED_EXTENSIONS`] like Vite does, as
+/// `import.meta.glob` only matches actual files. When the file can't be found,
+/// e.g. in SWC's wasm plugin runtime, which can't access the filesystem, the
+/// glob matches the specifier with any of these extensions instead, and
+/// `modules` contains the id without its extension.
+#[derive(Debug, Clone)]
+pub struct ViteCodegen {
+    root: PathBuf,
+    import_meta_glob: bool,
+}
+
+impl ViteCodegen {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        ViteCodegen {
+            root: root.into(),
+            import_meta_glob: true,
+        }
+    }
+
+    /// Only generates the `modules` option, for plain Rollup builds, which
+    /// don't support `import.meta.glob`.
+    pub fn without_import_meta_glob(mut self) -> Self {
+        self.import_meta_glob = false;
+        self
+    }
+}
+
+/// The extensions tried when resolving a specifier without one, the default
+/// `resolve.extensions` of Vite.
+pub const RESOLVED_EXTENSIONS: &[&str] = &["mjs", "js", "mts", "ts", "jsx", "tsx", "json"];
+
+impl DynamicCodegen for ViteCodegen {
+    fn generate(&self, call: &DynamicCallContext) -> DynamicCodegenOutput {
+        let FileName::Real(filename) = call.filename else {
+            return DynamicCodegenOutput {
+                props: module_id_options(call.module_id.into()),
+                module_items: vec![],
+            };
+        };
+
+        let resolved = resolve(call.module_id, filename);
+        let specifier = resolved.as_deref().unwrap_or(call.module_id);
+        let root_relative_id = paths::root_relative_id(&self.root, specifier, filename);
+
+        let mut props =
+            module_id_options(root_relative_id.as_deref().unwrap_or(call.module_id).into());
+        if self.import_meta_glob && root_relative_id.is_some() {
+            let glob = match resolved {
+                Some(resolved) => resolved,
+                None => format!("{specifier}.{{{}}}", RESOLVED_EXTENSIONS.join(",")),
+            };
+            props.push(vite_option(&glob));
+        }
+
+        DynamicCodegenOutput {
+            props,
+            module_items: vec![],
+        }
+    }
+}
+
+/// Resolves `specifier`, relative to the directory of `filename`, to the file
+/// it imports, and returns the specifier of this file, e.g.
+/// `../components/hello.tsx` or `../components/hello/index.tsx`. Returns `None`
+/// for specifiers which aren't relative paths or whose file can't be found.
+#[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+fn resolve(specifier: &str, filename: &Path) -> Option<String> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+
+    let dir = filename.parent()?;
+    if dir.join(specifier).is_file() {
+        return Some(specifier.to_string());
+    }
+    let specifier = specifier.trim_end_matches('/');
+    RESOLVED_EXTENSIONS
+        .iter()
+        .map(|extension| format!("{specifier}.{extension}"))
+        .chain(
+            RESOLVED_EXTENSIONS
+                .iter()
+                .map(|extension| format!("{specifier}/index.{extension}")),
+        )
+        .find(|candidate| dir.join(candidate).is_file())
+}
+
+#[cfg(any(feature = "wasm", target_arch = "wasm32"))]
+fn resolve(_specifier: &str, _filename: &Path) -> Option<String> {
+    None
+}
+
+/// Returns the `vite: () => Object.keys(import.meta.glob(glob))` option.
+fn vite_option(glob: &str) -> PropOrSpread {
+    let glob = Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::MetaProp(MetaPropExpr {
+            span: DUMMY_SP,
+            kind: MetaPropKind::ImportMeta,
+        })),
+        prop: MemberProp::Ident(Ident::new("glob".into(), DUMMY_SP)),
+    })
+    .as_call(DUMMY_SP, vec![glob.as_arg()]);
+    let keys = Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(Ident::new("Object".into(), DUMMY_SP))),
+        prop: MemberProp::Ident(Ident::new("keys".into(), DUMMY_SP)),
+    })
+    .as_call(DUMMY_SP, vec![glob.as_arg()]);
+
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("vite".into(), DUMMY_SP)),
+        value: Box::new(Expr::Arrow(ArrowExpr {
+            params: vec![],
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(keys))),
+            is_async: false,
+            is_generator: false,
+            span: DUMMY_SP,
+            return_type: None,
+            type_params: None,
+        })),
+    })))
+}
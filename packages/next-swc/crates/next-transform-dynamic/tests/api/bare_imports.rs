@@ -0,0 +1,56 @@
+use std::sync::{Arc, Mutex};
+
+use next_transform_dynamic::{
+    bare_imports::{bare_imports, BareImport},
+    Config,
+};
+use swc_core::{
+    common::{comments::SingleThreadedComments, sync::Lrc, FileName, SourceMap},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+#[test]
+fn bare_imports_skip_dynamic_calls() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "import dynamic from 'next/dist/shared/lib/dynamic'\nconst DynamicComponent = dynamic(() \
+         => import('../components/hello'))\nconst chart = () => \
+         import('../components/chart')\nconst settings = () => import('../pages/settings')\n"
+            .into(),
+    );
+    let comments = SingleThreadedComments::default();
+    let module = parse_module(&fm, Some(&comments));
+
+    let imports: Arc<Mutex<Vec<BareImport>>> = Default::default();
+    module.fold_with(&mut bare_imports(
+        imports.clone(),
+        Config::default(),
+        vec!["../components/*".into()],
+        Some(comments.clone()),
+    ));
+
+    assert_eq!(
+        *imports.lock().unwrap(),
+        [
+            BareImport {
+                specifier: "../components/chart".into()
+            },
+            BareImport {
+                specifier: "../pages/settings".into()
+            }
+        ]
+    );
+    // Only the imports opting in to prefetching are annotated.
+    let (leading, _) = comments.borrow_all();
+    assert_eq!(
+        leading
+            .values()
+            .flatten()
+            .map(|comment| &*comment.text)
+            .collect::<Vec<_>>(),
+        [" webpackPrefetch: true "]
+    );
+}
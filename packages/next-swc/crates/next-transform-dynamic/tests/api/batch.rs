@@ -0,0 +1,166 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use next_transform_dynamic::{batch::transform_batch, Config, NextDynamicMode};
+use swc_core::{
+    common::{
+        errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+        sync::Lrc,
+        FileName, SourceMap, GLOBALS,
+    },
+    ecma::ast::Program,
+};
+
+use crate::parse_module;
+
+fn parse(cm: &Lrc<SourceMap>, src: &str) -> Program {
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+    Program::Module(parse_module(&fm, None))
+}
+
+#[test]
+fn transforms_modules_in_order() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let modules = vec![
+        (
+            FileName::Real("/some-project/src/a.js".into()),
+            parse(
+                &cm,
+                "import dynamic from 'next/dynamic'\nconst A = dynamic(() => import('./a'))\n",
+            ),
+        ),
+        (
+            FileName::Real("/some-project/src/b.js".into()),
+            parse(&cm, "export const b = 1\n"),
+        ),
+        (
+            FileName::Real("/some-project/src/c.js".into()),
+            parse(
+                &cm,
+                "import dynamic from 'next/dynamic'\nconst C = dynamic(() => \
+                 import('./c'))\nconst D = dynamic(() => import('./d'))\n",
+            ),
+        ),
+    ];
+
+    let results = GLOBALS.set(&Default::default(), || {
+        transform_batch(
+            false,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            Some(PathBuf::from("/some-project/src").into()),
+            modules,
+            Arc::new(Config {
+                collect_diagnostics: true,
+                ..Default::default()
+            }),
+        )
+    });
+
+    let calls: Vec<_> = results
+        .iter()
+        .map(|result| (result.filename.to_string(), result.stats.calls_transformed))
+        .collect();
+    assert_eq!(
+        calls,
+        [
+            ("/some-project/src/a.js".to_string(), 1),
+            ("/some-project/src/b.js".to_string(), 0),
+            ("/some-project/src/c.js".to_string(), 2),
+        ]
+    );
+    assert!(results.iter().all(|result| result.diagnostics.is_empty()));
+}
+
+/// Many modules, split into several chunks with the `rayon` feature, where the
+/// odd ones report a diagnostic.
+fn many_modules(cm: &Lrc<SourceMap>) -> Vec<(FileName, Program)> {
+    (0..64)
+        .map(|index| {
+            let src = if index % 2 == 0 {
+                format!(
+                    "import dynamic from 'next/dynamic'\nconst A = dynamic(() => \
+                     import('./{index}'))\n"
+                )
+            } else {
+                "import dynamic from 'next/dynamic'\nconst A = dynamic(() => null)\n".to_string()
+            };
+            (
+                FileName::Real(format!("/some-project/src/{index}.js").into()),
+                parse(cm, &src),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn keeps_the_order_of_many_modules() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let modules = many_modules(&cm);
+
+    let results = GLOBALS.set(&Default::default(), || {
+        transform_batch(
+            false,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            Some(PathBuf::from("/some-project/src").into()),
+            modules,
+            Arc::new(Config {
+                collect_diagnostics: true,
+                ..Default::default()
+            }),
+        )
+    });
+
+    assert_eq!(results.len(), 64);
+    for (index, result) in results.iter().enumerate() {
+        assert_eq!(
+            result.filename.to_string(),
+            format!("/some-project/src/{index}.js")
+        );
+        assert_eq!(result.stats.calls_transformed, (index + 1) % 2);
+        assert_eq!(result.diagnostics.len(), index % 2);
+    }
+}
+
+#[derive(Clone, Default)]
+struct RecordingEmitter(Arc<Mutex<Vec<String>>>);
+
+impl Emitter for RecordingEmitter {
+    fn emit(&mut self, diagnostic: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push(diagnostic.message());
+    }
+}
+
+#[test]
+fn emits_the_diagnostics_in_the_order_of_the_modules() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let modules = many_modules(&cm);
+    let emitter = RecordingEmitter::default();
+    let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+
+    let results = GLOBALS.set(&Default::default(), || {
+        HANDLER.set(&handler, || {
+            transform_batch(
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                Some(PathBuf::from("/some-project/src").into()),
+                modules,
+                Default::default(),
+            )
+        })
+    });
+
+    assert!(results.iter().all(|result| result.diagnostics.is_empty()));
+    let emitted = emitter.0.lock().unwrap();
+    assert_eq!(emitted.len(), 32);
+    for (index, message) in emitted.iter().enumerate() {
+        assert!(message.starts_with(&format!("{}.js: ", index * 2 + 1)));
+    }
+}
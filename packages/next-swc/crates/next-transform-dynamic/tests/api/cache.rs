@@ -0,0 +1,283 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use next_transform_dynamic::{
+    cache::{cache_key, get_or_transform, transform_cached, CachedTransform, MemoryTransformCache},
+    ChunkResolver, Config, DiagnosticCode, DynamicCall, DynamicCallContext, DynamicCodegen,
+    DynamicCodegenOutput, LoadableKeyRegistry, ManifestEntry, NextDynamicMode,
+};
+use swc_core::common::{sync::Lrc, FileName, SourceFile, SourceMap, GLOBALS};
+
+#[test]
+fn cache_skips_unchanged_modules() {
+    let cache = MemoryTransformCache::new();
+    let filename = FileName::Real("/some-project/src/some-file.js".into());
+    let source = "import dynamic from 'next/dynamic'";
+    let output = CachedTransform {
+        code: source.into(),
+        manifest_entries: vec![],
+        diagnostics: vec![],
+        dynamic_calls: vec![],
+        keys: vec![],
+    };
+    let key = |source: &str,
+               is_development: bool,
+               mode: NextDynamicMode,
+               pages_dir: Option<&Path>,
+               config: Config| {
+        cache_key(
+            source,
+            &filename,
+            is_development,
+            false,
+            false,
+            &mode,
+            pages_dir,
+            &config,
+        )
+    };
+
+    let cached = key(
+        source,
+        true,
+        NextDynamicMode::Webpack,
+        None,
+        Config::default(),
+    );
+    assert_eq!(
+        get_or_transform(&cache, cached.unwrap(), || output.clone()),
+        output
+    );
+    assert_eq!(
+        get_or_transform(&cache, cached.unwrap(), || unreachable!(
+            "the output is cached"
+        )),
+        output
+    );
+
+    // Changing the content or the options of the transform changes the key.
+    assert_eq!(
+        key(
+            source,
+            true,
+            NextDynamicMode::Webpack,
+            None,
+            Config::default()
+        ),
+        cached
+    );
+    assert_ne!(
+        key("", true, NextDynamicMode::Webpack, None, Config::default()),
+        cached
+    );
+    assert_ne!(
+        key(
+            source,
+            false,
+            NextDynamicMode::Webpack,
+            None,
+            Config::default()
+        ),
+        cached
+    );
+    assert_ne!(
+        key(
+            source,
+            true,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-client-chunks".into(),
+                key_export: Default::default(),
+            },
+            None,
+            Config::default()
+        ),
+        cached
+    );
+    assert_ne!(
+        key(
+            source,
+            true,
+            NextDynamicMode::Webpack,
+            Some(Path::new("/some-project/src/pages")),
+            Config::default()
+        ),
+        cached
+    );
+    assert_ne!(
+        key(
+            source,
+            true,
+            NextDynamicMode::Webpack,
+            None,
+            Config {
+                env: HashMap::from([("NODE_ENV".to_string(), "production".to_string())]),
+                ..Default::default()
+            }
+        ),
+        cached
+    );
+    assert_ne!(
+        key(
+            source,
+            true,
+            NextDynamicMode::Webpack,
+            None,
+            Config {
+                chunk_resolver: Some(ChunkResolver::new(|_| None)),
+                ..Default::default()
+            }
+        ),
+        cached
+    );
+}
+
+#[derive(Debug)]
+struct Codegen(Option<&'static str>);
+
+impl DynamicCodegen for Codegen {
+    fn generate(&self, _call: &DynamicCallContext) -> DynamicCodegenOutput {
+        Default::default()
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        self.0.map(String::from)
+    }
+}
+
+#[test]
+fn cache_key_identifies_custom_codegens_by_their_cache_key() {
+    let key = |codegen: Codegen| {
+        cache_key(
+            "",
+            &FileName::Anon,
+            false,
+            false,
+            false,
+            &NextDynamicMode::Custom(Arc::new(codegen)),
+            None,
+            &Config::default(),
+        )
+    };
+
+    assert_eq!(key(Codegen(None)), None);
+    assert_eq!(key(Codegen(Some("a"))), key(Codegen(Some("a"))));
+    assert!(key(Codegen(Some("a"))).is_some());
+    assert_ne!(key(Codegen(Some("a"))), key(Codegen(Some("b"))));
+}
+
+/// Transforms `fm` with `transform_cached`, returning its output along with
+/// the manifest entries and the `dynamic()` calls collected by the sinks.
+fn transform(
+    cache: &MemoryTransformCache,
+    fm: &SourceFile,
+    key_registry: Option<Arc<LoadableKeyRegistry>>,
+) -> (CachedTransform, Vec<ManifestEntry>, Vec<DynamicCall>) {
+    let manifest_entries: Arc<Mutex<Vec<ManifestEntry>>> = Default::default();
+    let dynamic_calls: Arc<Mutex<Vec<DynamicCall>>> = Default::default();
+    let output = GLOBALS.set(&Default::default(), || {
+        transform_cached(
+            cache,
+            fm,
+            true,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            Some(PathBuf::from("/some-project/src")),
+            Config {
+                manifest_entries: Some(manifest_entries.clone()),
+                dynamic_calls: Some(dynamic_calls.clone()),
+                key_registry,
+                collect_diagnostics: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    });
+    let manifest_entries = manifest_entries.lock().unwrap().clone();
+    let dynamic_calls = dynamic_calls.lock().unwrap().clone();
+    (output, manifest_entries, dynamic_calls)
+}
+
+#[test]
+fn transform_cached_replays_the_metadata_of_cached_outputs() {
+    let cache = MemoryTransformCache::new();
+    let cm: Lrc<SourceMap> = Default::default();
+    let source = "/*! Some license */\nimport dynamic from 'next/dynamic'\nconst Hello = \
+                  dynamic(() => import(/* webpackChunkName: \"hello\" */ '../components/hello'), \
+                  { ssr: false, ssr: true })\n";
+
+    let fm = cm.new_source_file(
+        FileName::Real("/some-project/src/some-file.js".into()),
+        source.into(),
+    );
+    let (output, manifest_entries, dynamic_calls) = transform(&cache, &fm, None);
+    assert!(output.code.contains("loadableGenerated"));
+    assert!(output.code.contains("/*! Some license */"));
+    assert!(output.code.contains("/* webpackChunkName: \"hello\" */"));
+    assert_eq!(output.manifest_entries.len(), 1);
+    assert_eq!(manifest_entries, output.manifest_entries);
+    assert_eq!(output.dynamic_calls.len(), 1);
+    assert_eq!(dynamic_calls, output.dynamic_calls);
+    assert_eq!(output.diagnostics.len(), 1);
+
+    // The same module, loaded again, e.g. after being saved without changes.
+    let reloaded = cm.new_source_file(
+        FileName::Real("/some-project/src/some-file.js".into()),
+        source.into(),
+    );
+    let (cached, cached_manifest_entries, cached_dynamic_calls) =
+        transform(&cache, &reloaded, None);
+    assert_eq!(cached.code, output.code);
+    assert_eq!(cached_manifest_entries, manifest_entries);
+    assert_eq!(cached_dynamic_calls, dynamic_calls);
+    assert_eq!(cached.diagnostics.len(), 1);
+    assert_eq!(
+        cached.diagnostics[0].span.lo - reloaded.start_pos,
+        output.diagnostics[0].span.lo - fm.start_pos
+    );
+    assert_eq!(
+        cm.span_to_snippet(cached.diagnostics[0].span).unwrap(),
+        cm.span_to_snippet(output.diagnostics[0].span).unwrap()
+    );
+}
+
+#[test]
+fn transform_cached_registers_the_keys_of_cached_outputs() {
+    let cache = MemoryTransformCache::new();
+    let cm: Lrc<SourceMap> = Default::default();
+    let source = "import dynamic from 'next/dynamic'\nconst Hello = dynamic(() => \
+                  import('../components/hello'))\n";
+    let load = |filename: &str| cm.new_source_file(FileName::Real(filename.into()), source.into());
+
+    let (output, ..) = transform(&cache, &load("/some-project/src/some-file.js"), None);
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(output.keys.len(), 1);
+
+    // A module only differing in casing generates a colliding key, which is
+    // reported for the cached module too.
+    let key_registry = Arc::new(LoadableKeyRegistry::new());
+    let (other, ..) = transform(
+        &cache,
+        &load("/some-project/src/Some-File.js"),
+        Some(key_registry.clone()),
+    );
+    assert!(other.diagnostics.is_empty());
+    let (cached, ..) = transform(
+        &cache,
+        &load("/some-project/src/some-file.js"),
+        Some(key_registry),
+    );
+    assert_eq!(cached.code, output.code);
+    assert_eq!(cached.diagnostics.len(), 1);
+    assert_eq!(
+        cached.diagnostics[0].code,
+        DiagnosticCode::KeyRegistryCollision
+    );
+    assert_eq!(
+        cached.diagnostics[0].filename.as_deref(),
+        Some("some-file.js")
+    );
+}
@@ -0,0 +1,149 @@
+use next_transform_dynamic::{
+    codemod::{codemod, Rewrite},
+    Config,
+};
+use swc_core::common::FileName;
+
+fn filename() -> FileName {
+    FileName::Real("/some-project/src/some-file.js".into())
+}
+
+#[test]
+fn migrates_legacy_object_form() {
+    let output =
+        codemod(
+            "import dynamic from 'next/dynamic'\n\n// The hello component.\nconst Hello = \
+             dynamic({\n  loader: () => import('../components/hello'),\n  loading: () => null,\n  \
+             ssr: false,\n})\nconst World = dynamic({ loader: () => import('../components/world') \
+             })\nconst Merged = dynamic({ loader: () => import('../components/merged') }, { ssr: \
+             false })\nconst Both = dynamic({ loading: () => null, loader: () => \
+             import('../components/both') }, { ssr: false })\n",
+            filename(),
+            &Default::default(),
+            &[Rewrite::LegacyObjectForm],
+        )
+        .unwrap();
+
+    assert_eq!(
+        output.as_deref(),
+        Some(
+            "import dynamic from 'next/dynamic'\n\n// The hello component.\nconst Hello = \
+             dynamic(() => import('../components/hello'), {\n  loading: () => null,\n  ssr: \
+             false,\n})\nconst World = dynamic(() => import('../components/world'))\nconst Merged \
+             = dynamic(() => import('../components/merged'), { ssr: false })\nconst Both = \
+             dynamic(() => import('../components/both'), { loading: () => null, ssr: false })\n"
+        )
+    );
+}
+
+#[test]
+fn only_edits_the_rewritten_calls() {
+    let source = "import dynamic from 'next/dynamic'\n\nconst  unrelated = { loader: 1 }  // \
+                  keeps its formatting\n/* the world */ const World = dynamic({ loader: () => \
+                  import('../components/world') }) ;\nexport   default World\n";
+    let output = codemod(
+        source,
+        filename(),
+        &Default::default(),
+        &[Rewrite::LegacyObjectForm],
+    )
+    .unwrap()
+    .unwrap();
+
+    let call = "dynamic({ loader: () => import('../components/world') })";
+    let (before, after) = source.split_once(call).unwrap();
+    assert_eq!(
+        output,
+        format!("{before}dynamic(() => import('../components/world')){after}")
+    );
+}
+
+#[test]
+fn migrates_calls_of_configured_specifiers() {
+    let output = codemod(
+        "import dynamic from '@acme/dynamic'\nconst World = dynamic({ loader: () => \
+         import('./world') })\n",
+        filename(),
+        &Config {
+            dynamic_specifiers: Some(vec!["@acme/dynamic".into()]),
+            ..Default::default()
+        },
+        &[Rewrite::LegacyObjectForm],
+    )
+    .unwrap();
+
+    assert_eq!(
+        output.as_deref(),
+        Some(
+            "import dynamic from '@acme/dynamic'\nconst World = dynamic(() => import('./world'))\n"
+        )
+    );
+}
+
+#[test]
+fn leaves_modern_calls_untouched() {
+    let output = codemod(
+        "import dynamic from 'next/dynamic'\nconst Hello = dynamic(() => \
+         import('../components/hello'), { ssr: false })\nconst options = { loader: () => \
+         import('../components/world') }\nconst World = dynamic(options)\n",
+        filename(),
+        &Default::default(),
+        &[Rewrite::LegacyObjectForm],
+    )
+    .unwrap();
+
+    assert_eq!(output, None);
+}
+
+#[test]
+fn strips_stale_loadable_generated() {
+    let source = "import dynamic from 'next/dynamic'\n\n// Compiled by mistake.\nconst Hello = \
+                  dynamic(() => import('../components/hello'), {\n  loadableGenerated: { modules: \
+                  ['some-file.js -> ../components/hello'] },\n})\nconst World = dynamic(() => \
+                  import('../components/world'), {\n  loadableGenerated: {\n    webpack: () => \
+                  [require.resolveWeak('../components/world')],\n  },\n  ssr: false,\n})\nconst \
+                  Wrapped = dynamic(() => import('../components/wrapped'), { ssr: false, \
+                  loadableGenerated: { modules: ['wrapped'] } } satisfies \
+                  DynamicOptions)\n\nexport  {Hello,World}   // untouched\n";
+    let output = codemod(
+        source,
+        FileName::Real("/some-project/src/some-file.tsx".into()),
+        &Default::default(),
+        &[Rewrite::StaleLoadableGenerated],
+    )
+    .unwrap();
+
+    assert_eq!(
+        output.as_deref(),
+        Some(
+            "import dynamic from 'next/dynamic'\n\n// Compiled by mistake.\nconst Hello = \
+             dynamic(() => import('../components/hello'))\nconst World = dynamic(() => \
+             import('../components/world'), {\n  ssr: false,\n})\nconst Wrapped = dynamic(() => \
+             import('../components/wrapped'), { ssr: false } satisfies DynamicOptions)\n\nexport  \
+             {Hello,World}   // untouched\n"
+        )
+    );
+}
+
+#[test]
+fn strips_loadable_generated_of_migrated_calls() {
+    let output = codemod(
+        "import dynamic from 'next/dynamic'\nconst Hello = dynamic({\n  loader: () => \
+         import('../components/hello'),\n  loadableGenerated: { modules: ['hello'] },\n})\nconst \
+         World = dynamic({ loader: () => import('../components/world') }, { loadableGenerated: { \
+         modules: ['world'] }, ssr: false })\n/* unrelated */ foo( {loadableGenerated: 1} )\n",
+        FileName::Real("/some-project/src/some-file.js".into()),
+        &Default::default(),
+        &[Rewrite::LegacyObjectForm, Rewrite::StaleLoadableGenerated],
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "import dynamic from 'next/dynamic'\nconst Hello = dynamic(() => \
+         import('../components/hello'))\nconst World = dynamic(() => \
+         import('../components/world'), { ssr: false })\n/* unrelated */ foo( {loadableGenerated: \
+         1} )\n"
+    );
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use next_transform_dynamic::const_eval::eval_bool;
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, SyntaxContext},
+    ecma::{
+        ast::EsVersion,
+        parser::{parse_file_as_expr, Syntax},
+    },
+};
+
+fn eval(src: &str, env: &[(&str, &str)]) -> Option<bool> {
+    eval_with_declared(src, env, &[])
+}
+
+/// Evaluates `src` in a module declaring the `declared` bindings, without the
+/// `resolver`.
+fn eval_with_declared(src: &str, env: &[(&str, &str)], declared: &[&str]) -> Option<bool> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+    let expr = parse_file_as_expr(
+        &fm,
+        Syntax::default(),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    )
+    .unwrap();
+    let env: HashMap<String, String> = env
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    let declared = declared
+        .iter()
+        .map(|name| ((*name).into(), SyntaxContext::empty()))
+        .collect();
+
+    eval_bool(&expr, &env, &declared)
+}
+
+#[test]
+fn evaluates_literals() {
+    assert_eq!(eval("true", &[]), Some(true));
+    assert_eq!(eval("!0", &[]), Some(true));
+    assert_eq!(eval("!!false", &[]), Some(false));
+    assert_eq!(eval("Boolean('')", &[]), Some(false));
+    assert_eq!(eval("'yes'", &[]), None);
+}
+
+#[test]
+fn evaluates_definitions() {
+    let env = [("NEXT_PUBLIC_SSR", "true"), ("typeof window", "undefined")];
+
+    assert_eq!(
+        eval("process.env.NEXT_PUBLIC_SSR === 'true'", &env),
+        Some(true)
+    );
+    assert_eq!(eval("typeof window !== 'undefined'", &env), Some(false));
+    assert_eq!(eval("process.env.MISSING === 'true'", &env), None);
+    assert_eq!(eval("process.env.NEXT_PUBLIC_SSR == 1", &env), None);
+}
+
+#[test]
+fn evaluates_loose_comparisons_of_the_same_type_only() {
+    let env = [("NEXT_PUBLIC_SSR", "true")];
+
+    assert_eq!(
+        eval("process.env.NEXT_PUBLIC_SSR == 'true'", &env),
+        Some(true)
+    );
+    assert_eq!(
+        eval("process.env.NEXT_PUBLIC_SSR != 'true'", &env),
+        Some(false)
+    );
+    assert_eq!(eval("process.env.NEXT_PUBLIC_SSR == true", &env), None);
+    assert_eq!(eval("true == '1'", &[]), None);
+    assert_eq!(eval("true != '1'", &[]), None);
+    assert_eq!(eval("0 == ''", &[]), None);
+    assert_eq!(eval("true === '1'", &[]), Some(false));
+    assert_eq!(eval("1 !== '1'", &[]), Some(true));
+}
+
+#[test]
+fn doesnt_evaluate_shadowed_globals() {
+    let env = [("NEXT_PUBLIC_SSR", "true"), ("typeof window", "undefined")];
+
+    assert_eq!(eval("'' === undefined", &env), Some(false));
+    assert_eq!(
+        eval_with_declared("'' === undefined", &env, &["undefined"]),
+        None
+    );
+    assert_eq!(
+        eval_with_declared("process.env.NEXT_PUBLIC_SSR === 'true'", &env, &["process"]),
+        None
+    );
+    assert_eq!(
+        eval_with_declared("typeof window === 'undefined'", &env, &["window"]),
+        None
+    );
+    assert_eq!(eval_with_declared("Boolean(1)", &env, &["Boolean"]), None);
+    assert_eq!(
+        eval_with_declared("typeof window === 'undefined'", &env, &["undefined"]),
+        Some(true)
+    );
+}
@@ -0,0 +1,88 @@
+use next_transform_dynamic::{next_dynamic_patcher, InjectedImport, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::{
+        codegen::{text_writer::JsWriter, Emitter, Node},
+        visit::FoldWith,
+    },
+};
+
+use crate::parse_module;
+
+const SOURCE: &str = "import dynamic from 'next/dynamic'
+const A = dynamic(() => import('../components/a'))
+const B = dynamic(() => import('../components/b'), {
+  loading: dynamic(() => import('../components/c')),
+})
+const A2 = dynamic(() => import('../components/a'))
+const D = dynamic(() => import('../components/d'))
+";
+
+/// Transforms `SOURCE` from scratch, like a new build would, returning the
+/// printed module and the injected Turbopack imports.
+fn transform(is_development: bool, is_server_compiler: bool) -> (String, Vec<InjectedImport>) {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, SOURCE.into());
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            is_development,
+            is_server_compiler,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-client-chunks".into(),
+                key_export: Default::default(),
+            },
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Default::default(),
+        );
+        let module = module.fold_with(&mut patcher);
+
+        let mut buf = vec![];
+        {
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                comments: None,
+                wr: JsWriter::new(cm.clone(), "\n", &mut buf, None),
+            };
+            module.emit_with(&mut emitter).unwrap();
+        }
+        (
+            String::from_utf8(buf).unwrap(),
+            patcher.injected_items().imports.clone(),
+        )
+    })
+}
+
+#[test]
+fn imports_follow_the_source_order_of_calls() {
+    let (_, imports) = transform(false, false);
+
+    assert_eq!(
+        imports
+            .iter()
+            .map(|import| &*import.specifier)
+            .collect::<Vec<_>>(),
+        [
+            "../components/a",
+            "../components/b",
+            "../components/c",
+            "../components/d"
+        ]
+    );
+}
+
+#[test]
+fn output_is_identical_across_runs() {
+    for (is_development, is_server_compiler) in
+        [(true, false), (true, true), (false, false), (false, true)]
+    {
+        let (expected, _) = transform(is_development, is_server_compiler);
+        for _ in 0..10 {
+            assert_eq!(transform(is_development, is_server_compiler).0, expected);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use next_transform_dynamic::{
+    next_dynamic_patcher, Config, DiagnosticCode, DiagnosticLevel, DynamicDiagnostic,
+    NextDynamicMode,
+};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, DUMMY_SP, GLOBALS},
+    ecma::{
+        ast::EsVersion,
+        parser::{parse_file_as_script, Syntax},
+        visit::FoldWith,
+    },
+};
+
+use crate::parse_module;
+
+fn collect_diagnostics(
+    src: &str,
+    config: Config,
+) -> Result<Vec<DynamicDiagnostic>, Vec<DynamicDiagnostic>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            true,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Config {
+                collect_diagnostics: true,
+                ..config
+            },
+        );
+        module.fold_with(&mut patcher);
+        patcher.finish()
+    })
+}
+
+#[test]
+fn collects_errors_without_handler() {
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic()\n",
+        Default::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+    assert_eq!(
+        diagnostics[0].message,
+        "next/dynamic requires at least one argument"
+    );
+    assert_eq!(diagnostics[0].code, DiagnosticCode::NoArguments);
+    assert_eq!(diagnostics[0].code.as_str(), "next-dynamic/no-arguments");
+}
+
+#[test]
+fn collects_warnings_without_handler() {
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => null)\n",
+        Default::default(),
+    )
+    .unwrap();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+}
+
+#[test]
+fn formats_diagnostics_with_file_and_specifier() {
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic()\nconst Hello1 = \
+         dynamic(() => import('../components/hello'))\nconst Hello2 = dynamic(() => \
+         import('../components/Hello'))\n",
+        Default::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "some-file.js: next/dynamic requires at least one argument"
+    );
+    assert_eq!(diagnostics[1].filename.as_deref(), Some("some-file.js"));
+    assert_eq!(
+        diagnostics[1].specifier.as_deref(),
+        Some("../components/Hello")
+    );
+}
+
+#[test]
+fn resolves_overlay_code_frames() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let src = "import dynamic from 'next/dynamic'\n\nconst DynamicComponent = dynamic(() => \
+               import('../components/hello'), {}, {})";
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+    let module = parse_module(&fm, None);
+
+    let diagnostics = GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            true,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Config {
+                collect_diagnostics: true,
+                ..Default::default()
+            },
+        );
+        module.fold_with(&mut patcher);
+        patcher.finish().unwrap_err()
+    });
+
+    let overlay = diagnostics[0].to_overlay(&cm).unwrap();
+    assert_eq!(overlay.severity, DiagnosticLevel::Error);
+    assert_eq!(overlay.code, "next-dynamic/too-many-arguments");
+    assert_eq!(overlay.filename.as_deref(), Some("some-file.js"));
+    assert_eq!(
+        &src[overlay.start as usize..overlay.end as usize],
+        "dynamic"
+    );
+    assert_eq!((overlay.line, overlay.column), (3, 26));
+    assert_eq!((overlay.end_line, overlay.end_column), (3, 33));
+    assert_eq!(overlay.snippet_line, 2);
+    assert_eq!(
+        overlay.snippet,
+        "\nconst DynamicComponent = dynamic(() => import('../components/hello'), {}, {})"
+    );
+    assert_eq!(overlay.docs_url, None);
+}
+
+#[test]
+fn doesnt_resolve_overlay_code_frames_without_span() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'), {}, {})\n",
+        Default::default(),
+    )
+    .unwrap_err();
+
+    let diagnostic = DynamicDiagnostic {
+        span: DUMMY_SP,
+        ..diagnostics[0].clone()
+    };
+    assert_eq!(diagnostic.to_overlay(&cm), None);
+}
+
+#[test]
+fn strict_mode_reports_warnings_as_errors() {
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => null)\n",
+        Config {
+            strict: true,
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+}
+
+#[test]
+fn labels_the_first_of_duplicate_options() {
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'), { ssr: false, ssr: true })\n",
+        Default::default(),
+    )
+    .unwrap();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].labels.len(), 1);
+    assert_eq!(diagnostics[0].labels[0].1, "first set here");
+    assert!(diagnostics[0].labels[0].0.lo < diagnostics[0].span.lo);
+}
+
+#[test]
+fn reports_usage_in_edge_entries() {
+    let diagnostics = collect_diagnostics(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'))\n",
+        Config {
+            is_edge_entry: true,
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0]
+        .message
+        .starts_with("next/dynamic is not supported in middleware"));
+}
+
+#[test]
+fn reports_scripts_needing_imports() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "const dynamic = require('next/dynamic')\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'))\n"
+            .into(),
+    );
+    let script = parse_file_as_script(
+        &fm,
+        Syntax::Es(Default::default()),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    )
+    .unwrap();
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            true,
+            false,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-client-chunks".into(),
+                key_export: Default::default(),
+            },
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Config {
+                collect_diagnostics: true,
+                ..Default::default()
+            },
+        );
+        script.fold_with(&mut patcher);
+
+        let diagnostics = patcher.finish().unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0]
+            .message
+            .starts_with("next/dynamic can't add the imports"));
+    });
+}
+
+#[test]
+fn collects_diagnostics_without_handler() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic()\n".into(),
+    );
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            true,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Default::default(),
+        );
+        module.fold_with(&mut patcher);
+
+        assert_eq!(patcher.finish().unwrap_err().len(), 1);
+    });
+}
@@ -0,0 +1,193 @@
+use std::{path::PathBuf, sync::Arc};
+
+use next_transform_dynamic::{
+    dual::{transform_dual, DualOutput},
+    next_dynamic_patcher_shared, Config, DiagnosticCode, DynamicDiagnostic, NextDynamicMode,
+    TransformStats,
+};
+use swc_core::{
+    common::{sync::Lrc, EqIgnoreSpan, FileName, SourceMap, Span, DUMMY_SP, GLOBALS},
+    ecma::{
+        ast::Program,
+        visit::{FoldWith, VisitMut, VisitMutWith},
+    },
+};
+
+use crate::parse_module;
+
+fn parse(src: &str) -> Program {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+    Program::Module(parse_module(&fm, None))
+}
+
+fn turbopack_mode() -> NextDynamicMode {
+    NextDynamicMode::Turbopack {
+        dynamic_transition_names: "next-client-chunks".into(),
+        key_export: Default::default(),
+    }
+}
+
+fn config() -> Arc<Config> {
+    Arc::new(Config {
+        collect_diagnostics: true,
+        ..Default::default()
+    })
+}
+
+fn transform(program: Program) -> DualOutput {
+    transform_dual(
+        false,
+        false,
+        false,
+        turbopack_mode(),
+        FileName::Real("/some-project/src/some-file.js".into()),
+        Some(PathBuf::from("/some-project/src").into()),
+        program,
+        config(),
+    )
+}
+
+/// Folds `program` in `mode` alone, like a build with a single bundler,
+/// returning its output along with the stats and the diagnostics of the
+/// transform.
+fn fold_alone(
+    mode: NextDynamicMode,
+    program: Program,
+) -> (Program, TransformStats, Vec<DynamicDiagnostic>) {
+    let mut patcher = next_dynamic_patcher_shared(
+        false,
+        false,
+        false,
+        mode,
+        FileName::Real("/some-project/src/some-file.js".into()),
+        Some(PathBuf::from("/some-project/src").into()),
+        config(),
+    );
+    let program = program.fold_with(&mut patcher);
+    (
+        program,
+        patcher.stats(),
+        patcher.pending_diagnostics().to_vec(),
+    )
+}
+
+fn fold(mode: NextDynamicMode, program: Program) -> Program {
+    fold_alone(mode, program).0
+}
+
+/// Checks the stats of a transform, except for its duration.
+fn assert_stats_eq(stats: TransformStats, expected: TransformStats) {
+    assert_eq!(stats.calls_transformed, expected.calls_transformed);
+    assert_eq!(stats.items_injected, expected.items_injected);
+    assert_eq!(stats.diagnostics_emitted, expected.diagnostics_emitted);
+}
+
+/// Removes the spans of a program, like the ones generated by earlier
+/// transforms.
+struct DropSpans;
+
+impl VisitMut for DropSpans {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = DUMMY_SP;
+    }
+}
+
+#[test]
+fn shares_the_output_of_modules_without_next_dynamic() {
+    let output = GLOBALS.set(&Default::default(), || {
+        transform(parse("import React from 'react'\nexport const a = 1\n"))
+    });
+
+    assert!(matches!(output, DualOutput::Shared(..)));
+    assert!(std::ptr::eq(output.webpack(), output.turbopack()));
+}
+
+#[test]
+fn transforms_modules_with_next_dynamic_for_each_backend() {
+    let output = GLOBALS.set(&Default::default(), || {
+        transform(parse(
+            "import dynamic from 'next/dynamic'\nconst A = dynamic(() => import('./a'))\n",
+        ))
+    });
+
+    let DualOutput::Split { webpack, turbopack } = output else {
+        panic!("expected the module to be transformed for each backend");
+    };
+    assert_eq!(webpack.stats.calls_transformed, 1);
+    assert_eq!(webpack.stats.items_injected, 0);
+    assert!(webpack.injected_items.imports.is_empty());
+    assert_eq!(turbopack.stats.calls_transformed, 1);
+    assert_eq!(turbopack.stats.items_injected, 2);
+    assert_eq!(turbopack.injected_items.imports.len(), 1);
+    assert_eq!(turbopack.injected_items.imports[0].specifier, "./a");
+}
+
+#[test]
+fn matches_the_output_of_each_backend_alone() {
+    let program = parse(
+        "import dynamic from 'next/dynamic'\nconst A = dynamic(() => import('./a'))\nconst B = \
+         dynamic(() => import('./b'), { ssr: false })\n",
+    );
+
+    GLOBALS.set(&Default::default(), || {
+        let output = transform(program.clone());
+
+        assert_eq!(
+            *output.webpack(),
+            fold(NextDynamicMode::Webpack, program.clone())
+        );
+        // The idents of the Turbopack imports are private to each transform.
+        assert!(output
+            .turbopack()
+            .eq_ignore_span(&fold(turbopack_mode(), program)));
+    });
+}
+
+#[test]
+fn reports_the_stats_and_the_diagnostics_of_each_backend() {
+    // The keys of the two calls collide, which is only reported for Webpack.
+    let program = parse(
+        "import dynamic from 'next/dynamic'\nconst A = dynamic(() => import('./a'), { ssr: false, \
+         ssr: true })\nconst B = dynamic(() => import('./A'))\n",
+    );
+
+    GLOBALS.set(&Default::default(), || {
+        let DualOutput::Split { webpack, turbopack } = transform(program.clone()) else {
+            panic!("expected the module to be transformed for each backend");
+        };
+        let (_, webpack_stats, webpack_diagnostics) =
+            fold_alone(NextDynamicMode::Webpack, program.clone());
+        let (_, turbopack_stats, turbopack_diagnostics) = fold_alone(turbopack_mode(), program);
+
+        assert_stats_eq(webpack.stats, webpack_stats);
+        assert_eq!(webpack.diagnostics, webpack_diagnostics);
+        assert_stats_eq(turbopack.stats, turbopack_stats);
+        assert_eq!(turbopack.diagnostics, turbopack_diagnostics);
+
+        assert_eq!(webpack.diagnostics.len(), 2);
+        assert_eq!(webpack.diagnostics[1].code, DiagnosticCode::KeyCollision);
+        assert_eq!(turbopack.diagnostics.len(), 1);
+    });
+}
+
+#[test]
+fn matches_the_output_of_each_backend_alone_without_spans() {
+    let mut program = parse(
+        "import dynamic from 'next/dynamic'\nconst A = dynamic(() => import('./a'))\nconst B = \
+         dynamic(() => import('./b'), { ssr: false })\n",
+    );
+    program.visit_mut_with(&mut DropSpans);
+
+    GLOBALS.set(&Default::default(), || {
+        let output = transform(program.clone());
+
+        assert_eq!(
+            *output.webpack(),
+            fold(NextDynamicMode::Webpack, program.clone())
+        );
+        assert!(output
+            .turbopack()
+            .eq_ignore_span(&fold(turbopack_mode(), program)));
+    });
+}
@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use next_transform_dynamic::{
+    edge_runtime::check_edge_runtime, DiagnosticLevel, DynamicDiagnostic,
+};
+use swc_core::{
+    common::{sync::Lrc, FileName, Mark, SourceMap, GLOBALS},
+    ecma::{transforms::base::resolver, visit::VisitMutWith},
+};
+
+use crate::parse_module;
+
+fn filename() -> FileName {
+    FileName::Real("/some-project/src/pages/index.js".into())
+}
+
+fn diagnostics(src: &str) -> Vec<DynamicDiagnostic> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(filename(), src.into());
+    let mut module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let unresolved_mark = Mark::new();
+        module.visit_mut_with(&mut resolver(unresolved_mark, Mark::new(), false));
+        check_edge_runtime(
+            &module,
+            &filename(),
+            Some(Path::new("/some-project/src")),
+            unresolved_mark,
+        )
+    })
+}
+
+fn check(src: &str) -> Vec<String> {
+    diagnostics(src)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn reports_node_apis() {
+    let messages = check(
+        "import fs from 'node:fs'\nconst os = require('os')\nexport const cwd = \
+         process.cwd()\nexport const buffer = Buffer.from('')\n",
+    );
+
+    assert_eq!(messages.len(), 4);
+    assert!(messages[0].starts_with("The Node.js module \"node:fs\""));
+    assert!(messages[1].starts_with("The Node.js module \"os\""));
+    assert!(messages[2].starts_with("process.cwd"));
+    assert!(messages[3].starts_with("The Buffer global"));
+}
+
+#[test]
+fn allows_edge_apis() {
+    let messages = check(
+        "import { Buffer } from 'buffer'\nexport const buffer = Buffer.from('')\nexport const env \
+         = process.env.NODE_ENV\n",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn allows_local_bindings_shadowing_node_globals() {
+    let messages = check(
+        "const process = { cwd: () => '/' }\nexport const cwd = process.cwd()\nexport function \
+         from(Buffer) {\n  return Buffer.from('')\n}\nexport const buffer = Buffer.from('')\n",
+    );
+
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].starts_with("The Buffer global"));
+}
+
+#[test]
+fn reports_the_file_relative_to_the_pages_dir() {
+    let diagnostics = diagnostics("export const cwd = process.cwd()\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].filename.as_deref(), Some("pages/index.js"));
+}
+
+#[test]
+fn reports_guarded_node_apis_as_warnings() {
+    let diagnostics = diagnostics(
+        "if (process.env.NEXT_RUNTIME === 'nodejs') {\n  require('fs')\n}\nexport const cwd = \
+         typeof process.cwd === 'function' ? process.cwd() : '/'\nexport const buffer = typeof \
+         Buffer !== 'undefined' && Buffer.from('')\ntry {\n  require('os')\n} catch {}\nexport \
+         const uptime = process.uptime()\n",
+    );
+
+    assert_eq!(diagnostics.len(), 5);
+    assert!(diagnostics[0]
+        .message
+        .starts_with("The Node.js module \"fs\""));
+    assert!(diagnostics[1].message.starts_with("process.cwd"));
+    assert!(diagnostics[2].message.starts_with("The Buffer global"));
+    assert!(diagnostics[3]
+        .message
+        .starts_with("The Node.js module \"os\""));
+    assert!(diagnostics[4].message.starts_with("process.uptime"));
+    let levels = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.level)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            DiagnosticLevel::Warning,
+            DiagnosticLevel::Warning,
+            DiagnosticLevel::Warning,
+            DiagnosticLevel::Warning,
+            DiagnosticLevel::Error,
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use next_transform_dynamic::{next_dynamic_patcher, Config, DynamicEventSink, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, Span, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+#[derive(Debug, Default)]
+struct RecordingSink {
+    events: Mutex<Vec<String>>,
+}
+
+impl DynamicEventSink for RecordingSink {
+    fn on_call_detected(&self, _filename: &FileName, specifier: &str, _span: Span) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("call {specifier}"));
+    }
+
+    fn on_key_generated(&self, _filename: &FileName, key: &str, _span: Span) {
+        self.events.lock().unwrap().push(format!("key {key}"));
+    }
+
+    fn on_loader_stripped(&self, _filename: &FileName, _span: Span) {
+        self.events.lock().unwrap().push("loader stripped".into());
+    }
+}
+
+#[test]
+fn next_dynamic_event_sink() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm
+        .load_file(&PathBuf::from("tests/fixture/with-options/input.js"))
+        .unwrap();
+    let module = parse_module(&fm, None);
+
+    let sink = Arc::new(RecordingSink::default());
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            false,
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Config {
+                event_sink: Some(sink.clone()),
+                ..Default::default()
+            },
+        );
+        module.fold_with(&mut patcher);
+    });
+
+    assert_eq!(
+        *sink.events.lock().unwrap(),
+        [
+            "call ../components/hello",
+            "key some-file.js -> ../components/hello",
+            "call ../components/hello",
+            "key some-file.js -> ../components/hello",
+            "loader stripped",
+            "call ../components/hello",
+            "key some-file.js -> ../components/hello",
+        ]
+    );
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use next_transform_dynamic::{next_dynamic_patcher, Config, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+fn transform(filename: &str) -> usize {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm
+        .load_file(&PathBuf::from("tests/fixture/with-options/input.js"))
+        .unwrap();
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            true,
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real(filename.into()),
+            Some("/some-project/src".into()),
+            Config {
+                exclude: vec!["*/node_modules/*".into(), "*.generated.js".into()],
+                ..Default::default()
+            },
+        );
+        module.fold_with(&mut patcher);
+        patcher.stats().calls_transformed
+    })
+}
+
+#[test]
+fn skips_excluded_modules() {
+    assert_eq!(
+        transform("/some-project/node_modules/some-package/index.js"),
+        0
+    );
+    assert_eq!(transform("/some-project/src/routes.generated.js"), 0);
+}
+
+#[test]
+fn transforms_other_modules() {
+    assert_eq!(transform("/some-project/src/some-file.js"), 3);
+}
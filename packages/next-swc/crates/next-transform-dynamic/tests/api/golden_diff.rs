@@ -0,0 +1,47 @@
+use next_transform_dynamic::golden_diff::golden_diff;
+use swc_core::common::FileName;
+
+#[test]
+fn golden_diff_compares_modes() {
+    let calls = golden_diff(
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'), { ssr: false })\n",
+        FileName::Real("/some-project/src/some-file.js".into()),
+        true,
+        false,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(calls.len(), 1);
+    let modes: Vec<_> = calls[0].options.iter().map(|(mode, _)| *mode).collect();
+    assert_eq!(modes, ["webpack", "turbopack", "babel-compat"]);
+    assert!(calls[0]
+        .options
+        .iter()
+        .all(|(_, options)| options.contains("loadableGenerated")));
+
+    // Babel adds `loadableGenerated` after the other options.
+    assert!(calls[0].differs());
+}
+
+#[test]
+fn golden_diff_compares_calls_of_custom_specifiers() {
+    let calls = golden_diff(
+        "import dynamic from '@acme/dynamic'\nimport nextDynamic from 'next/dynamic'\nconst A = \
+         dynamic<Props>(() => import('../components/a'))\nconst B = nextDynamic(() => \
+         import('../components/b'))\n",
+        FileName::Real("/some-project/src/some-file.ts".into()),
+        true,
+        false,
+        Some(vec!["@acme/dynamic".into()]),
+    )
+    .unwrap();
+
+    // Only the call of the custom specifier is compared.
+    assert_eq!(calls.len(), 1);
+    assert!(calls[0]
+        .options
+        .iter()
+        .all(|(_, options)| options.contains("loadableGenerated")));
+}
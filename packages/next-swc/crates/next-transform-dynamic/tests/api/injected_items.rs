@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use next_transform_dynamic::{
+    next_dynamic_patcher, Config, InjectedImport, InjectedItems, NextDynamicMode,
+};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+fn injected_items(
+    is_server_compiler: bool,
+    mode: NextDynamicMode,
+    config: Config,
+) -> InjectedItems {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm
+        .load_file(&PathBuf::from("tests/fixture/duplicated-imports/input.js"))
+        .unwrap();
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            false,
+            is_server_compiler,
+            false,
+            mode,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            config,
+        );
+        module.fold_with(&mut patcher);
+        patcher.injected_items().clone()
+    })
+}
+
+#[test]
+fn next_dynamic_injected_turbopack_imports() {
+    let mode = NextDynamicMode::Turbopack {
+        dynamic_transition_names: "next-dynamic".into(),
+        key_export: Default::default(),
+    };
+
+    let server = injected_items(true, mode.clone(), Default::default());
+    assert_eq!(
+        server.imports,
+        [
+            InjectedImport {
+                specifier: "../components/hello1".into(),
+                transition: Some("next-dynamic".into()),
+            },
+            InjectedImport {
+                specifier: "../components/hello2".into(),
+                transition: Some("next-dynamic".into()),
+            },
+        ]
+    );
+    assert!(server.helper_imports.is_empty());
+
+    let client = injected_items(false, mode, Default::default());
+    assert!(client
+        .imports
+        .iter()
+        .all(|import| import.transition.is_none()));
+}
+
+#[test]
+fn next_dynamic_injected_helper_imports() {
+    let items = injected_items(
+        true,
+        NextDynamicMode::Webpack,
+        Config {
+            react_use: true,
+            ..Default::default()
+        },
+    );
+
+    assert!(items.imports.is_empty());
+    assert_eq!(
+        items.helper_imports,
+        ["react", "next/dist/shared/lib/loader-promise-cache"]
+    );
+}
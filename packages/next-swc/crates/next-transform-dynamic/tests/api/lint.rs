@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use next_transform_dynamic::{lint::lint, DynamicDiagnostic};
+use swc_core::common::{sync::Lrc, FileName, SourceMap};
+
+use crate::parse_module;
+
+fn filename() -> FileName {
+    FileName::Real("/some-project/src/pages/index.js".into())
+}
+
+fn diagnostics(src: &str) -> Vec<DynamicDiagnostic> {
+    diagnostics_with_pages_dir(src, Path::new("/some-project/src"))
+}
+
+fn diagnostics_with_pages_dir(src: &str, pages_dir: &Path) -> Vec<DynamicDiagnostic> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(filename(), src.into());
+    let module = parse_module(&fm, None);
+
+    lint(&module, &filename(), Some(pages_dir))
+}
+
+fn check(src: &str) -> Vec<String> {
+    diagnostics(src)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn reports_lint_violations() {
+    let messages = check(
+        "export default function Page() {\n  return <div>\n    <img src=\"/hello.png\" />\n    <a \
+         href=\"/about\">About</a>\n    <script src=\"/analytics.js\" />\n  </div>\n}\n",
+    );
+
+    assert_eq!(messages.len(), 3);
+    assert!(messages[0].starts_with("Using `<img>`"));
+    assert!(messages[1].starts_with("Do not use an `<a>` element to navigate to `/about`"));
+    assert!(messages[2].starts_with("Synchronous scripts should not be used"));
+}
+
+#[test]
+fn allows_external_links_and_async_scripts() {
+    let messages = check(
+        "export default function Page() {\n  return <div>\n    <a \
+         href=\"https://nextjs.org\">Next.js</a>\n    <a href=\"//example.com\">Example</a>\n    \
+         <script src=\"/analytics.js\" async />\n    <script>{'inline'}</script>\n  </div>\n}\n",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn reports_links_to_the_pages_only() {
+    let pages_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/lint/pages");
+    let messages = diagnostics_with_pages_dir(
+        "export default function Page() {\n  return <div>\n    <a href=\"/\">Home</a>\n    <a \
+         href=\"/about?tab=team\">About</a>\n    <a href=\"/blog/hello\">Blog</a>\n    <a \
+         href=\"/docs\">Docs</a>\n    <a href=\"/contact\">Contact</a>\n    <a \
+         href=\"/blog/hello/comments\">Comments</a>\n    <a href=\"/api/hello\">API</a>\n  \
+         </div>\n}\n",
+        &pages_dir,
+    )
+    .into_iter()
+    .map(|diagnostic| diagnostic.message)
+    .collect::<Vec<_>>();
+
+    assert_eq!(messages.len(), 4);
+    assert!(messages[0].starts_with("Do not use an `<a>` element to navigate to `/`"));
+    assert!(messages[1].starts_with("Do not use an `<a>` element to navigate to `/about?tab=team`"));
+    assert!(messages[2].starts_with("Do not use an `<a>` element to navigate to `/blog/hello`"));
+    assert!(messages[3].starts_with("Do not use an `<a>` element to navigate to `/docs`"));
+}
+
+#[test]
+fn allows_links_to_api_routes_and_files_without_pages() {
+    let messages =
+        check(
+            "export default function Page() {\n  return <div>\n    <a \
+             href=\"/api/hello\">API</a>\n    <a \
+             href=\"/_next/static/chunks/main.js\">Chunk</a>\n    <a \
+             href=\"/robots.txt\">Robots</a>\n  </div>\n}\n",
+        );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn reports_the_file_relative_to_the_pages_dir() {
+    let diagnostics =
+        diagnostics("export default function Page() {\n  return <img src=\"/hello.png\" />\n}\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].filename.as_deref(), Some("pages/index.js"));
+}
@@ -0,0 +1,73 @@
+//! Tests of the APIs of the transform, other than its output, which is covered
+//! by the fixtures.
+
+use std::path::Path;
+
+use next_transform_dynamic::{next_dynamic, Config, NextDynamicMode};
+use swc_core::{
+    common::{comments::Comments, sync::Lrc, FileName, SourceFile, SourceMap, GLOBALS},
+    ecma::{
+        ast::{EsVersion, Module},
+        parser::{parse_file_as_module, EsConfig, Syntax},
+        visit::FoldWith,
+    },
+};
+
+mod bare_imports;
+mod batch;
+mod cache;
+mod codemod;
+mod const_eval;
+mod determinism;
+mod diagnostics;
+mod dual;
+mod edge_runtime;
+mod event_sink;
+mod exclude;
+mod golden_diff;
+mod injected_items;
+mod lint;
+mod manifest_entries;
+mod mode;
+mod react_compiler;
+mod reset;
+mod root_dir;
+mod route_graph;
+mod split_report;
+mod stats;
+mod testing_feature;
+mod vite;
+
+/// Parses `fm` as a module with JSX, adding its comments to `comments`.
+fn parse_module(fm: &SourceFile, comments: Option<&dyn Comments>) -> Module {
+    parse_file_as_module(
+        fm,
+        Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        comments,
+        &mut vec![],
+    )
+    .unwrap()
+}
+
+/// Transforms the `input` of a fixture in Webpack mode, for the side effects
+/// of the transform on the `config`.
+fn transform_fixture(input: &Path, is_development: bool, is_server_compiler: bool, config: Config) {
+    let cm: Lrc<SourceMap> = Default::default();
+    let module = parse_module(&cm.load_file(input).unwrap(), None);
+
+    GLOBALS.set(&Default::default(), || {
+        module.fold_with(&mut next_dynamic(
+            is_development,
+            is_server_compiler,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            config,
+        ));
+    });
+}
@@ -0,0 +1,54 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use next_transform_dynamic::{Config, KeyBase, ManifestEntry};
+
+use crate::transform_fixture;
+
+#[test]
+fn next_dynamic_manifest_entries() {
+    let manifest_entries: Arc<Mutex<Vec<ManifestEntry>>> = Default::default();
+    transform_fixture(
+        Path::new("tests/fixture/with-options/input.js"),
+        true,
+        false,
+        Config {
+            manifest_entries: Some(manifest_entries.clone()),
+            ..Default::default()
+        },
+    );
+
+    // All three calls import the same module, so they share a single entry.
+    let manifest_entries = manifest_entries.lock().unwrap();
+    assert!(!manifest_entries.is_empty());
+    assert!(manifest_entries.iter().all(|entry| entry
+        == &ManifestEntry {
+            key: "some-file.js -> ../components/hello".into(),
+            specifier: "../components/hello".into(),
+            file: "/some-project/src/some-file.js".into(),
+            key_base: KeyBase::PagesDir,
+            original_key: None,
+        }));
+}
+
+#[test]
+fn next_dynamic_max_key_length() {
+    let manifest_entries: Arc<Mutex<Vec<ManifestEntry>>> = Default::default();
+    transform_fixture(
+        Path::new("tests/fixture/max-key-length/input.js"),
+        false,
+        true,
+        Config {
+            manifest_entries: Some(manifest_entries.clone()),
+            max_key_length: Some(20),
+            ..Default::default()
+        },
+    );
+
+    let manifest_entries = manifest_entries.lock().unwrap();
+    assert!(!manifest_entries.is_empty());
+    assert!(manifest_entries.iter().all(|entry| entry.key == "142zfsrc"
+        && entry.original_key.as_deref() == Some("some-file.js -> ../components/hello")));
+}
@@ -0,0 +1,103 @@
+use next_transform_dynamic::{
+    DynamicRuntime, NextDynamicMode, TransitionLayer, TransitionNames, TurbopackKeyExport,
+};
+
+#[test]
+fn mode_round_trips_through_strings() {
+    for (s, mode) in [
+        ("webpack", NextDynamicMode::Webpack),
+        (
+            "turbopack:next-dynamic",
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-dynamic".into(),
+                key_export: Default::default(),
+            },
+        ),
+        (
+            "turbopack:next-dynamic,edge-server=next-dynamic-edge",
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: TransitionNames::new("next-dynamic")
+                    .with_layer(TransitionLayer::EdgeServer, "next-dynamic-edge"),
+                key_export: Default::default(),
+            },
+        ),
+        (
+            "hybrid:next-client-chunks:default",
+            NextDynamicMode::Hybrid {
+                dynamic_transition_names: "next-client-chunks".into(),
+                key_export: TurbopackKeyExport::Default,
+            },
+        ),
+    ] {
+        assert_eq!(s.parse::<NextDynamicMode>().unwrap(), mode);
+        assert_eq!(mode.to_string(), s);
+        assert_eq!(
+            serde_json::from_str::<NextDynamicMode>(&serde_json::to_string(&mode).unwrap())
+                .unwrap(),
+            mode
+        );
+    }
+}
+
+#[test]
+fn mode_rejects_invalid_strings() {
+    for s in [
+        "",
+        "turbopack",
+        "turbopack:",
+        "rspack:next-dynamic",
+        "webpack:next-dynamic",
+        "turbopack:next-dynamic,server",
+        "turbopack:next-dynamic,server=",
+        "turbopack:next-dynamic,unknown-layer=next-dynamic-edge",
+    ] {
+        assert!(
+            s.parse::<NextDynamicMode>().is_err(),
+            "{s} should be invalid"
+        );
+    }
+}
+
+#[test]
+fn mode_error_lists_the_available_modes() {
+    let message = "vite".parse::<NextDynamicMode>().unwrap_err().to_string();
+
+    assert!(message.starts_with("invalid next/dynamic mode \"vite\", expected \"webpack\", "));
+    assert_eq!(message.contains("\"rspack\""), cfg!(feature = "rspack"));
+    assert!(message.ends_with(
+        "\"turbopack:<transitions>[:<key export>]\" or \"hybrid:<transitions>[:<key export>]\""
+    ));
+}
+
+#[test]
+fn mode_accessors() {
+    let mode = NextDynamicMode::Hybrid {
+        dynamic_transition_names: "next-client-chunks".into(),
+        key_export: TurbopackKeyExport::Default,
+    };
+    assert_eq!(
+        mode.dynamic_transition_names(),
+        Some(&TransitionNames::new("next-client-chunks"))
+    );
+    assert_eq!(mode.key_export(), Some(&TurbopackKeyExport::Default));
+    assert!(mode.has_webpack_keys());
+
+    assert_eq!(NextDynamicMode::Webpack.dynamic_transition_names(), None);
+    assert!(NextDynamicMode::Webpack.has_webpack_keys());
+}
+
+#[test]
+fn transition_layer_from_options() {
+    assert_eq!(
+        TransitionLayer::new(false, false, DynamicRuntime::Edge),
+        TransitionLayer::Client
+    );
+    assert_eq!(
+        TransitionLayer::new(true, false, DynamicRuntime::Edge),
+        TransitionLayer::EdgeServer
+    );
+    assert_eq!(
+        TransitionLayer::new(true, true, DynamicRuntime::Nodejs),
+        TransitionLayer::ReactServer
+    );
+}
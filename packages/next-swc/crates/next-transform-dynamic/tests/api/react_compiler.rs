@@ -0,0 +1,74 @@
+use next_transform_dynamic::{next_dynamic_patcher, Config, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::{
+        ast::{BlockStmtOrExpr, Expr, Lit, Module, Stmt},
+        visit::FoldWith,
+    },
+};
+
+use crate::parse_module;
+
+fn transform(react_compiler: bool) -> Module {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'))\n"
+            .into(),
+    );
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            false,
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Config {
+                react_use: true,
+                react_compiler,
+                ..Default::default()
+            },
+        );
+        module.fold_with(&mut patcher)
+    })
+}
+
+/// Returns the body of the component generated for `DynamicComponent`.
+fn component_body(module: &Module) -> &BlockStmtOrExpr {
+    let var = module
+        .body
+        .iter()
+        .find_map(|item| item.as_stmt()?.as_decl()?.as_var())
+        .unwrap();
+    match var.decls[0].init.as_deref() {
+        Some(Expr::Arrow(arrow)) => &arrow.body,
+        init => panic!("expected a component, got {init:?}"),
+    }
+}
+
+#[test]
+fn opts_out_of_memoization() {
+    let module = transform(true);
+
+    let BlockStmtOrExpr::BlockStmt(body) = component_body(&module) else {
+        panic!("expected a block body");
+    };
+    match &body.stmts[0] {
+        Stmt::Expr(stmt) => match &*stmt.expr {
+            Expr::Lit(Lit::Str(directive)) => assert_eq!(&*directive.value, "use no memo"),
+            expr => panic!("expected a directive, got {expr:?}"),
+        },
+        stmt => panic!("expected a directive, got {stmt:?}"),
+    }
+}
+
+#[test]
+fn memoizes_without_react_compiler() {
+    let module = transform(false);
+
+    assert!(matches!(component_body(&module), BlockStmtOrExpr::Expr(..)));
+}
@@ -0,0 +1,44 @@
+use std::{path::PathBuf, sync::Arc};
+
+use next_transform_dynamic::{next_dynamic_patcher_shared, Config, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+#[test]
+fn reset_patcher_transforms_another_module() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm
+        .load_file(&PathBuf::from("tests/fixture/with-options/input.js"))
+        .unwrap();
+    let module = parse_module(&fm, None);
+    let config = Arc::new(Config::default());
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher_shared(
+            true,
+            false,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-client-chunks".into(),
+                key_export: Default::default(),
+            },
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some(PathBuf::from("/some-project/src").into()),
+            config.clone(),
+        );
+        module.clone().fold_with(&mut patcher);
+        let injected_items = patcher.injected_items().clone();
+
+        patcher.reset(FileName::Real("/some-project/src/other-file.js".into()));
+        module.fold_with(&mut patcher);
+
+        let stats = patcher.stats();
+        assert_eq!(stats.calls_transformed, 3);
+        assert_eq!(stats.items_injected, 2);
+        assert_eq!(patcher.injected_items(), &injected_items);
+    });
+}
@@ -0,0 +1,61 @@
+use std::sync::{Arc, Mutex};
+
+use next_transform_dynamic::{
+    next_dynamic_patcher, Config, KeyBase, ManifestEntry, NextDynamicMode,
+};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+fn manifest_entries(filename: &str) -> Vec<ManifestEntry> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('./hello'))\n"
+            .into(),
+    );
+    let module = parse_module(&fm, None);
+
+    let manifest_entries: Arc<Mutex<Vec<ManifestEntry>>> = Default::default();
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            false,
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            FileName::Real(filename.into()),
+            Some("/workspace/apps/web/pages".into()),
+            Config {
+                manifest_entries: Some(manifest_entries.clone()),
+                root_dir: Some("/workspace".into()),
+                ..Default::default()
+            },
+        );
+        module.fold_with(&mut patcher);
+    });
+
+    let manifest_entries = manifest_entries.lock().unwrap();
+    manifest_entries.clone()
+}
+
+#[test]
+fn keys_outside_pages_dir_are_relative_to_root_dir() {
+    let entries = manifest_entries("/workspace/packages/ui/src/button.js");
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, "packages/ui/src/button.js -> ./hello");
+    assert_eq!(entries[0].key_base, KeyBase::RootDir);
+}
+
+#[test]
+fn keys_inside_pages_dir_are_relative_to_pages_dir() {
+    let entries = manifest_entries("/workspace/apps/web/pages/index.js");
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, "index.js -> ./hello");
+    assert_eq!(entries[0].key_base, KeyBase::PagesDir);
+}
@@ -0,0 +1,48 @@
+use next_transform_dynamic::route_graph::DynamicEntryGraphBuilder;
+
+#[test]
+fn route_graph_collects_reachable_dynamic_imports() {
+    let mut builder = DynamicEntryGraphBuilder::new();
+    builder
+        .add_module("app/layout.js", vec!["components/nav.js".into()], vec![])
+        .add_module(
+            "components/nav.js",
+            vec![],
+            vec!["components/menu.js".into()],
+        )
+        .add_module(
+            "components/menu.js",
+            vec![],
+            vec!["components/submenu.js".into()],
+        )
+        .add_module(
+            "app/page.js",
+            vec!["app/layout.js".into()],
+            vec!["components/chart.js".into()],
+        )
+        .add_module("app/about/page.js", vec!["app/layout.js".into()], vec![])
+        .add_route("/", vec!["app/page.js".into(), "app/layout.js".into()])
+        .add_route("/about", vec!["app/about/page.js".into()]);
+
+    let graph = builder.build();
+
+    let index = &graph.routes["/"];
+    assert_eq!(
+        index.dynamic_modules.iter().collect::<Vec<_>>(),
+        [
+            "components/chart.js",
+            "components/menu.js",
+            "components/submenu.js"
+        ]
+    );
+    assert_eq!(
+        index.importers.iter().collect::<Vec<_>>(),
+        ["app/page.js", "components/menu.js", "components/nav.js"]
+    );
+
+    let about = &graph.routes["/about"];
+    assert_eq!(
+        about.dynamic_modules.iter().collect::<Vec<_>>(),
+        ["components/menu.js", "components/submenu.js"]
+    );
+}
@@ -0,0 +1,105 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use next_transform_dynamic::{
+    route_graph::DynamicEntryGraphBuilder,
+    split_report::{split_report, RouteDynamicImport},
+    Config, DynamicCall,
+};
+
+use crate::transform_fixture;
+
+#[test]
+fn next_dynamic_collects_calls() {
+    let dynamic_calls: Arc<Mutex<Vec<DynamicCall>>> = Default::default();
+    transform_fixture(
+        Path::new("tests/fixture/with-options/input.js"),
+        true,
+        false,
+        Config {
+            dynamic_calls: Some(dynamic_calls.clone()),
+            ..Default::default()
+        },
+    );
+
+    let dynamic_calls = dynamic_calls.lock().unwrap();
+    assert_eq!(dynamic_calls.len(), 3);
+    assert!(dynamic_calls
+        .iter()
+        .all(|call| call.specifier == "../components/hello"
+            && call.file == "/some-project/src/some-file.js"));
+    assert_eq!(
+        dynamic_calls
+            .iter()
+            .map(|call| call.ssr)
+            .collect::<Vec<_>>(),
+        [true, false, false]
+    );
+}
+
+#[test]
+fn split_report_aggregates_calls_per_route() {
+    let mut builder = DynamicEntryGraphBuilder::new();
+    builder
+        .add_module(
+            "app/page.js",
+            vec!["components/nav.js".into()],
+            vec!["components/chart.js".into()],
+        )
+        .add_module(
+            "components/nav.js",
+            vec![],
+            vec!["components/menu.js".into()],
+        )
+        .add_module(
+            "app/about/page.js",
+            vec!["components/nav.js".into()],
+            vec![],
+        )
+        .add_route("/", vec!["app/page.js".into()])
+        .add_route("/about", vec!["app/about/page.js".into()]);
+
+    let call = |file: &str, specifier: &str, ssr| DynamicCall {
+        specifier: specifier.into(),
+        file: file.into(),
+        ssr,
+    };
+    let calls = [
+        call("app/page.js", "../components/chart", false),
+        call("components/nav.js", "./menu", false),
+        call("components/nav.js", "./menu", true),
+    ];
+    let resolve = |call: &DynamicCall| match &*call.specifier {
+        "../components/chart" => Some("components/chart.js".to_string()),
+        "./menu" => Some("components/menu.js".to_string()),
+        _ => None,
+    };
+    let sizes = HashMap::from([("components/chart.js".to_string(), 2048)]);
+
+    let report = split_report(&builder.build(), &calls, resolve, &sizes);
+
+    let index = &report.routes["/"];
+    assert_eq!(
+        index.dynamic_imports,
+        [
+            RouteDynamicImport {
+                module: "components/chart.js".into(),
+                ssr: false,
+                estimated_size: Some(2048),
+            },
+            RouteDynamicImport {
+                module: "components/menu.js".into(),
+                ssr: true,
+                estimated_size: None,
+            },
+        ]
+    );
+    assert_eq!(index.total_estimated_size, 2048);
+
+    let about = &report.routes["/about"];
+    assert_eq!(about.dynamic_imports.len(), 1);
+    assert_eq!(about.total_estimated_size, 0);
+}
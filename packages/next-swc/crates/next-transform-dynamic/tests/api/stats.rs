@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use next_transform_dynamic::{next_dynamic_patcher, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+#[test]
+fn next_dynamic_stats() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm
+        .load_file(&PathBuf::from("tests/fixture/with-options/input.js"))
+        .unwrap();
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            true,
+            false,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-client-chunks".into(),
+                key_export: Default::default(),
+            },
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Default::default(),
+        );
+        module.fold_with(&mut patcher);
+
+        let stats = patcher.stats();
+        assert_eq!(stats.calls_transformed, 3);
+        // A single import of the module, along with its Turbopack directive.
+        assert_eq!(stats.items_injected, 2);
+        assert_eq!(stats.diagnostics_emitted, 0);
+    });
+}
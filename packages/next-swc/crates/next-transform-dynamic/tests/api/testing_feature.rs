@@ -0,0 +1,77 @@
+#![cfg(feature = "testing")]
+
+use next_transform_dynamic::{next_dynamic_patcher, Config, NextDynamicMode};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::visit::FoldWith,
+};
+
+use crate::parse_module;
+
+#[test]
+fn exposes_patcher_state() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'))\nconst InvalidComponent = dynamic(() => null)\n"
+            .into(),
+    );
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            false,
+            false,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-dynamic".into(),
+                key_export: Default::default(),
+            },
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Config {
+                collect_diagnostics: true,
+                ..Default::default()
+            },
+        );
+        module.fold_with(&mut patcher);
+
+        assert_eq!(patcher.dynamic_bindings().len(), 1);
+        assert_eq!(&*patcher.dynamic_bindings()[0].0, "dynamic");
+        assert_eq!(patcher.pending_diagnostics().len(), 1);
+    });
+}
+
+#[test]
+fn exposes_turbopack_imports_before_they_are_added() {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Anon,
+        "import dynamic from 'next/dynamic'\nconst DynamicComponent = dynamic(() => \
+         import('../components/hello'))\n"
+            .into(),
+    );
+    let module = parse_module(&fm, None);
+
+    GLOBALS.set(&Default::default(), || {
+        let mut patcher = next_dynamic_patcher(
+            false,
+            false,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_names: "next-dynamic".into(),
+                key_export: Default::default(),
+            },
+            FileName::Real("/some-project/src/some-file.js".into()),
+            Some("/some-project/src".into()),
+            Default::default(),
+        );
+        // Folding the items one by one doesn't add the imports to the module.
+        for item in module.body {
+            item.fold_with(&mut patcher);
+        }
+
+        assert_eq!(patcher.turbopack_imports(), ["../components/hello"]);
+    });
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use next_transform_dynamic::{
+    vite::{ViteCodegen, RESOLVED_EXTENSIONS},
+    DynamicCallContext, DynamicCodegen,
+};
+use swc_core::{
+    common::{FileName, DUMMY_SP},
+    ecma::ast::{BlockStmtOrExpr, Expr, Lit, Prop, PropName, PropOrSpread},
+};
+
+const ROOT: &str = "tests/fixture/vite";
+
+/// Returns the `modules` and the `import.meta.glob` pattern generated for
+/// `specifier`, imported from the pages of the `vite` fixture.
+fn generate(specifier: &str) -> (String, String) {
+    let filename = FileName::Real(Path::new(ROOT).join("src/pages/some-file.js"));
+    let output = ViteCodegen::new(ROOT).generate(&DynamicCallContext {
+        module_id: specifier,
+        span: DUMMY_SP,
+        filename: &filename,
+        pages_dir: None,
+        is_development: true,
+        is_server_compiler: false,
+        is_react_server_layer: false,
+    });
+
+    let mut module = None;
+    let mut glob = None;
+    for prop in &output.props {
+        let PropOrSpread::Prop(prop) = prop else {
+            continue;
+        };
+        let Prop::KeyValue(prop) = &**prop else {
+            continue;
+        };
+        match (&prop.key, &*prop.value) {
+            (PropName::Ident(key), Expr::Array(modules)) if &*key.sym == "modules" => {
+                module = Some(string(&modules.elems[0].as_ref().unwrap().expr));
+            }
+            (PropName::Ident(key), Expr::Arrow(vite)) if &*key.sym == "vite" => {
+                let BlockStmtOrExpr::Expr(keys) = &*vite.body else {
+                    unreachable!();
+                };
+                let glob_call = &keys.as_call().unwrap().args[0].expr;
+                glob = Some(string(&glob_call.as_call().unwrap().args[0].expr));
+            }
+            _ => {}
+        }
+    }
+    (module.unwrap(), glob.unwrap())
+}
+
+fn string(expr: &Expr) -> String {
+    match expr {
+        Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+        _ => panic!("expected a string literal, got {expr:?}"),
+    }
+}
+
+/// Returns the files of the `vite` fixture matched by `glob`, relative to its
+/// pages, like `import.meta.glob` does: the `{a,b}` alternatives are expanded,
+/// and only the existing files are matched.
+fn import_meta_glob(glob: &str) -> Vec<PathBuf> {
+    let patterns = match glob.split_once('{') {
+        Some((prefix, rest)) => {
+            let (alternatives, suffix) = rest.split_once('}').unwrap();
+            alternatives
+                .split(',')
+                .map(|alternative| format!("{prefix}{alternative}{suffix}"))
+                .collect()
+        }
+        None => vec![glob.to_string()],
+    };
+    patterns
+        .into_iter()
+        .map(|pattern| Path::new(ROOT).join("src/pages").join(pattern))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+#[test]
+fn import_meta_glob_matches_the_imported_file() {
+    for (specifier, expected) in [
+        ("../components/hello", "src/components/hello.tsx"),
+        ("../components/chart", "src/components/chart/index.jsx"),
+    ] {
+        let (module, glob) = generate(specifier);
+
+        assert_eq!(module, expected);
+        let matched = import_meta_glob(&glob);
+        assert_eq!(matched.len(), 1, "{glob} matched {matched:?}");
+        assert_eq!(
+            matched[0].canonicalize().unwrap(),
+            Path::new(ROOT).join(expected).canonicalize().unwrap()
+        );
+    }
+}
+
+#[test]
+fn import_meta_glob_falls_back_to_the_resolved_extensions() {
+    let (module, glob) = generate("../components/missing");
+
+    assert_eq!(module, "src/components/missing");
+    assert_eq!(
+        glob,
+        format!(
+            "../components/missing.{{{}}}",
+            RESOLVED_EXTENSIONS.join(",")
+        )
+    );
+    assert!(import_meta_glob(&glob).is_empty());
+    assert_eq!(
+        import_meta_glob("../components/hello.{mjs,js,mts,ts,jsx,tsx,json}").len(),
+        1
+    );
+}
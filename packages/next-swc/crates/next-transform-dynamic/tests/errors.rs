@@ -25,7 +25,8 @@ fn next_dynamic_errors(input: PathBuf) {
         &input,
         "output-turbo.js",
         NextDynamicMode::Turbopack {
-            dynamic_transition_name: "next-client-chunks".into(),
+            dynamic_transition_names: "next-client-chunks".into(),
+            key_export: Default::default(),
         },
     );
 }
@@ -42,6 +43,7 @@ fn next_dynamic_errors_run(input: &Path, output: &str, mode: NextDynamicMode) {
                 mode.clone(),
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         input,
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+const key = 'ssr'
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  [key]: false,
+})
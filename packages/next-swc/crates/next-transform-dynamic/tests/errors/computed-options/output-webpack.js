@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+const key = 'ssr';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    [key]: false
+});
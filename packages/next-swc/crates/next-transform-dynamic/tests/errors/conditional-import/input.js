@@ -0,0 +1,6 @@
+if (typeof window !== 'undefined') {
+  const dynamic = require('next/dynamic')
+  const DynamicComponent = dynamic(() => import('../components/hello'))
+}
+
+const loadDynamic = () => import('next/dynamic')
@@ -0,0 +1,13 @@
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+if (typeof window !== 'undefined') {
+    const dynamic = require('next/dynamic');
+    const DynamicComponent = dynamic(()=>import('../components/hello'), {
+        loadableGenerated: {
+            modules: [
+                id
+            ]
+        }
+    });
+}
+const loadDynamic = ()=>import('next/dynamic');
@@ -0,0 +1,11 @@
+if (typeof window !== 'undefined') {
+    const dynamic = require('next/dynamic');
+    const DynamicComponent = dynamic(()=>import('../components/hello'), {
+        loadableGenerated: {
+            modules: [
+                "some-file.js -> " + "../components/hello"
+            ]
+        }
+    });
+}
+const loadDynamic = ()=>import('next/dynamic');
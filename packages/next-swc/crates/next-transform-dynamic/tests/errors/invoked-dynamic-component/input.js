@@ -0,0 +1,5 @@
+import { memo } from 'react'
+import dynamic from 'next/dynamic'
+
+const element = dynamic(() => import('../components/hello'))()
+const memoized = memo(dynamic(() => import('../components/hello')))()
@@ -0,0 +1,18 @@
+import { memo } from 'react';
+import dynamic from 'next/dynamic';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+const element = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+})();
+const memoized = memo(dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+}))();
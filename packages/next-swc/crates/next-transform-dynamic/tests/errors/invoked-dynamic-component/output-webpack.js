@@ -0,0 +1,16 @@
+import { memo } from 'react';
+import dynamic from 'next/dynamic';
+const element = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+})();
+const memoized = memo(dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+}))();
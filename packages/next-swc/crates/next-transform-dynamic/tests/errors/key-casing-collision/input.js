@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+
+const Hello1 = dynamic(() => import('../components/hello'))
+const Hello2 = dynamic(() => import('../components/Hello'))
@@ -0,0 +1,19 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id1 } from "../components/Hello";
+const Hello1 = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+});
+const Hello2 = dynamic(()=>import('../components/Hello'), {
+    loadableGenerated: {
+        modules: [
+            id1
+        ]
+    }
+});
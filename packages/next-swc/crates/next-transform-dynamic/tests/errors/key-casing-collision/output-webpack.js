@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic';
+const Hello1 = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+const Hello2 = dynamic(()=>import('../components/Hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/Hello"
+        ]
+    }
+});
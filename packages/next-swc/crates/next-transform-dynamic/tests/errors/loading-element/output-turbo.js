@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    },
+    loading: <p >...</p>
+});
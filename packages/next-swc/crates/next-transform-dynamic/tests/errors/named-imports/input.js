@@ -0,0 +1,2 @@
+import dynamic, { noSSR, preload, DynamicOptions } from 'next/dynamic'
+const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,8 @@
+import dynamic, { noSSR, preload, DynamicOptions } from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+});
@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/bye";
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id1 } from "../components/hello";
+const DynamicComponent = dynamic(()=>import('../components/hello').then(()=>dynamic(()=>import('../components/bye'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+})), {
+    loadableGenerated: {
+        modules: [
+            id1
+        ]
+    }
+});
@@ -0,0 +1,14 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello').then(()=>dynamic(()=>import('../components/bye'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/bye"
+        ]
+    }
+})), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+});
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>require('../components/hello'), {
+    loadableGenerated: {
+        modules: [],
+        error: "next/dynamic requires the loader to contain an `import()` expression, e.g. `dynamic(() => import('./component'))`"
+    }
+});
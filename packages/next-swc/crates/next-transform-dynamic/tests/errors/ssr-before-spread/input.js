@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic'
+
+const common = { loading: () => null }
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  ssr: false,
+  ...common,
+})
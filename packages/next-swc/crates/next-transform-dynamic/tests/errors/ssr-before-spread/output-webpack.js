@@ -0,0 +1,13 @@
+import dynamic from 'next/dynamic';
+const common = {
+    loading: ()=>null
+};
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false,
+    ...common
+});
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  srr: false,
+  loadin: () => null,
+  custom: true,
+})
@@ -1,11 +1,20 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use next_transform_dynamic::{next_dynamic, NextDynamicMode};
+use next_transform_dynamic::{
+    dead_branches::eliminate_dead_branches, display_names::display_names, next_dynamic,
+    next_dynamic_pass, next_dynamic_with_comments, vite::ViteCodegen, ChunkResolver, Config,
+    DynamicRuntime, GeneratedOptionsHook, NextDynamicMode, TransitionLayer, TransitionNames,
+    TurbopackKeyExport, WebpackResolver,
+};
 use swc_core::{
-    common::FileName,
+    common::{FileName, DUMMY_SP},
     ecma::{
-        parser::{EsConfig, Syntax},
-        transforms::testing::{test, test_fixture},
+        ast::{Ident, KeyValueProp, Prop, PropName, PropOrSpread},
+        parser::{EsConfig, Syntax, TsConfig},
+        transforms::testing::test_fixture,
     },
 };
 use testing::fixture;
@@ -17,7 +26,14 @@ fn syntax() -> Syntax {
     })
 }
 
-#[fixture("tests/fixture/**/input.js")]
+// The fixtures of a single option are only transformed by their own function
+// below.
+#[fixture(
+    "tests/fixture/**/input.js",
+    exclude(
+        "/(amp-only|base-url|chunk-resolver|class-members|compiled-es-module|dead-branches|deep-path-imports|default-ssr|display-names|eager-specifiers|hybrid|injection-order|inline-specifiers|loading-hints|magic-comments|max-key-length|minify-keys|non-streaming|pass|posix-paths|spread-options|ssr-literals|string-keys|test-stub|transform-generated|transition-names|turbopack-hygiene|vite|webpack-resolver|wrapped-callee)/"
+    )
+)]
 fn next_dynamic_fixture(input: PathBuf) {
     next_dynamic_fixture_run(
         &input,
@@ -51,7 +67,8 @@ fn next_dynamic_fixture(input: PathBuf) {
         false,
         false,
         NextDynamicMode::Turbopack {
-            dynamic_transition_name: "next-client-chunks".into(),
+            dynamic_transition_names: "next-client-chunks".into(),
+            key_export: Default::default(),
         },
     );
     next_dynamic_fixture_run(
@@ -61,7 +78,8 @@ fn next_dynamic_fixture(input: PathBuf) {
         true,
         false,
         NextDynamicMode::Turbopack {
-            dynamic_transition_name: "next-client-chunks".into(),
+            dynamic_transition_names: "next-client-chunks".into(),
+            key_export: Default::default(),
         },
     );
     next_dynamic_fixture_run(
@@ -71,7 +89,8 @@ fn next_dynamic_fixture(input: PathBuf) {
         false,
         false,
         NextDynamicMode::Turbopack {
-            dynamic_transition_name: "next-dynamic".into(),
+            dynamic_transition_names: "next-dynamic".into(),
+            key_export: Default::default(),
         },
     );
     next_dynamic_fixture_run(
@@ -81,7 +100,8 @@ fn next_dynamic_fixture(input: PathBuf) {
         true,
         false,
         NextDynamicMode::Turbopack {
-            dynamic_transition_name: "next-dynamic".into(),
+            dynamic_transition_names: "next-dynamic".into(),
+            key_export: Default::default(),
         },
     );
     next_dynamic_fixture_run(
@@ -91,11 +111,76 @@ fn next_dynamic_fixture(input: PathBuf) {
         true,
         true,
         NextDynamicMode::Turbopack {
-            dynamic_transition_name: "next-dynamic".into(),
+            dynamic_transition_names: "next-dynamic".into(),
+            key_export: Default::default(),
         },
     );
 }
 
+/// Checks that the output matches the one of the legacy `next/babel` plugin on
+/// the shared fixtures.
+#[fixture(
+    "tests/fixture/**/input.js",
+    exclude(
+        "/(amp-only|base-url|chunk-resolver|class-members|compiled-es-module|dead-branches|deep-path-imports|default-ssr|display-names|eager-specifiers|hybrid|injection-order|inline-specifiers|loading-hints|magic-comments|max-key-length|minify-keys|non-streaming|pass|posix-paths|spread-options|ssr-literals|string-keys|test-stub|transform-generated|transition-names|turbopack-hygiene|vite|webpack-resolver|wrapped-callee)/"
+    )
+)]
+fn next_dynamic_babel_compat(input: PathBuf) {
+    let babel_compat = || Config {
+        babel_compat: true,
+        ..Default::default()
+    };
+    next_dynamic_config_run(
+        &input,
+        "output-babel-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &babel_compat,
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-babel-prod.js",
+        false,
+        false,
+        NextDynamicMode::Webpack,
+        &babel_compat,
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-babel-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &babel_compat,
+    );
+}
+
+#[fixture("tests/typescript/**/input.ts", exclude("/preserve-types/"))]
+fn next_dynamic_typescript_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.ts");
+    test_fixture(
+        Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        }),
+        &|_tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.ts")),
+                Some("/some-project/src".into()),
+                Default::default(),
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
 fn next_dynamic_fixture_run(
     input: &Path,
     output: &str,
@@ -115,6 +200,7 @@ fn next_dynamic_fixture_run(
                 mode.clone(),
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 Some("/some-project/src".into()),
+                Default::default(),
             )
         },
         input,
@@ -122,3 +208,598 @@ fn next_dynamic_fixture_run(
         Default::default(),
     );
 }
+
+/// Transforms `input` outside of the React Server layer with the options
+/// returned by `config`.
+fn next_dynamic_config_run(
+    input: &Path,
+    output: &str,
+    is_development: bool,
+    is_server_compiler: bool,
+    mode: NextDynamicMode,
+    config: &dyn Fn() -> Config,
+) {
+    let output = input.parent().unwrap().join(output);
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            next_dynamic(
+                is_development,
+                is_server_compiler,
+                false,
+                mode.clone(),
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
+                Some("/some-project/src".into()),
+                config(),
+            )
+        },
+        input,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/amp-only/input.js")]
+fn next_dynamic_amp_only(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            is_amp_only: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/base-url/input.js")]
+fn next_dynamic_base_url(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            next_dynamic(
+                false,
+                true,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/pages/some-file.js")),
+                Some("/some-project/src/pages".into()),
+                Config {
+                    paths: vec![("~/*".into(), vec!["./src/*".into()])],
+                    base_url: Some("/some-project".into()),
+                    ..Default::default()
+                },
+            )
+        },
+        &input,
+        &input.with_file_name("output-webpack-server.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/chunk-resolver/input.js")]
+fn next_dynamic_chunk_resolver(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            chunk_resolver: Some(ChunkResolver::new(|module_id| {
+                (module_id == "../components/hello")
+                    .then(|| vec!["static/chunks/components_hello.js".into()])
+            })),
+            ..Default::default()
+        },
+    );
+}
+
+// Component registries assign dynamic components in static blocks and
+// decorated class members, which are transformed like module-scope calls.
+#[fixture("tests/fixture/class-members/input.js")]
+fn next_dynamic_class_members(input: PathBuf) {
+    test_fixture(
+        Syntax::Es(EsConfig {
+            jsx: true,
+            decorators: true,
+            ..Default::default()
+        }),
+        &|_tr| {
+            next_dynamic(
+                false,
+                true,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
+                Some("/some-project/src".into()),
+                Default::default(),
+            )
+        },
+        &input,
+        &input.with_file_name("output-webpack-server.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/compiled-es-module/input.js")]
+#[fixture("tests/fixture/deep-path-imports/input.js")]
+#[fixture("tests/fixture/ssr-literals/input.js")]
+#[fixture("tests/fixture/string-keys/input.js")]
+fn next_dynamic_server(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &Config::default,
+    );
+}
+
+#[fixture("tests/fixture/dead-branches/input.js")]
+fn next_dynamic_dead_branches(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            eliminate_dead_branches(
+                [
+                    ("NODE_ENV".to_string(), "production".to_string()),
+                    ("typeof window".to_string(), "object".to_string()),
+                ]
+                .into(),
+            )
+        },
+        &input,
+        &input.with_file_name("output.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/default-ssr/input.js")]
+fn next_dynamic_default_ssr(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            default_ssr: Some(false),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/display-names/input.js")]
+fn next_dynamic_display_names(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|_tr| display_names(Default::default()),
+        &input,
+        &input.with_file_name("output.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/eager-specifiers/input.js")]
+fn next_dynamic_eager_specifiers(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            eager_specifiers: vec!["../components/hero*".into()],
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/hybrid/input.js")]
+fn next_dynamic_hybrid(input: PathBuf) {
+    let mode = NextDynamicMode::Hybrid {
+        dynamic_transition_names: "next-client-chunks".into(),
+        key_export: Default::default(),
+    };
+    next_dynamic_config_run(
+        &input,
+        "output-dev-client.js",
+        true,
+        false,
+        mode.clone(),
+        &Config::default,
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-build-client.js",
+        false,
+        false,
+        mode,
+        &Config::default,
+    );
+}
+
+// The eager imports come before the Turbopack imports, after the directives
+// and the imports of the module.
+#[fixture("tests/fixture/injection-order/input.js")]
+fn next_dynamic_injection_order(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            eager_specifiers: vec!["../components/hero".into()],
+            ..Default::default()
+        },
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-turbo-dev-client.js",
+        true,
+        false,
+        NextDynamicMode::Turbopack {
+            dynamic_transition_names: "next-client-chunks".into(),
+            key_export: Default::default(),
+        },
+        &Config::default,
+    );
+}
+
+#[fixture("tests/fixture/inline-specifiers/input.js")]
+fn next_dynamic_inline_specifiers(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            inline_specifiers: vec!["../components/hero*".into()],
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/loading-hints/input.js")]
+fn next_dynamic_loading_hints(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &Config::default,
+    );
+}
+
+#[fixture("tests/fixture/magic-comments/input.js")]
+fn next_dynamic_magic_comments(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic_with_comments(
+                true,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
+                Some("/some-project/src".into()),
+                Default::default(),
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &input.with_file_name("output-webpack-dev.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/max-key-length/input.js")]
+fn next_dynamic_max_key_length(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            max_key_length: Some(20),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/minify-keys/input.js")]
+fn next_dynamic_minify_keys(input: PathBuf) {
+    let minify_keys = || Config {
+        minify_keys: true,
+        ..Default::default()
+    };
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &minify_keys,
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        true,
+        NextDynamicMode::Webpack,
+        &minify_keys,
+    );
+    // Production client builds don't emit keys.
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-prod.js",
+        false,
+        false,
+        NextDynamicMode::Webpack,
+        &minify_keys,
+    );
+}
+
+#[fixture("tests/fixture/non-streaming/input.js")]
+fn next_dynamic_non_streaming(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-turbo-dev-server.js",
+        true,
+        true,
+        NextDynamicMode::Turbopack {
+            dynamic_transition_names: "next-client-chunks".into(),
+            key_export: Default::default(),
+        },
+        &|| Config {
+            non_streaming: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/pass/input.js")]
+fn next_dynamic_pass_matches_fold(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            next_dynamic_pass(
+                true,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
+                Some("/some-project/src".into()),
+                Default::default(),
+            )
+        },
+        &input,
+        &input.with_file_name("output-webpack-dev.js"),
+        Default::default(),
+    );
+}
+
+/// Normalized filenames, as passed by SWC's wasm plugin runtime, generate the
+/// same keys as real paths.
+#[fixture("tests/fixture/posix-paths/input.js")]
+fn next_dynamic_custom_filename(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            next_dynamic(
+                false,
+                true,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Custom("/some-project/src/./some-file.js".into()),
+                Some("/some-project/src".into()),
+                Default::default(),
+            )
+        },
+        &input,
+        &input.with_file_name("output-webpack-server.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/typescript/preserve-types/input.ts")]
+fn next_dynamic_preserve_types(input: PathBuf) {
+    let run = |output: &str, is_development: bool, is_server_compiler: bool, test_stub: bool| {
+        test_fixture(
+            Syntax::Typescript(TsConfig {
+                tsx: true,
+                ..Default::default()
+            }),
+            &|_tr| {
+                next_dynamic(
+                    is_development,
+                    is_server_compiler,
+                    false,
+                    NextDynamicMode::Webpack,
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.ts")),
+                    Some("/some-project/src".into()),
+                    Config {
+                        preserve_types: true,
+                        test_stub,
+                        ..Default::default()
+                    },
+                )
+            },
+            &input,
+            &input.with_file_name(output),
+            Default::default(),
+        );
+    };
+    run("output-webpack-server.ts", false, true, false);
+    run("output-test-stub.ts", true, false, true);
+}
+
+#[fixture("tests/fixture/spread-options/input.js")]
+fn next_dynamic_spread_options(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            collect_diagnostics: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/test-stub/input.js")]
+fn next_dynamic_test_stub(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-dev.js",
+        true,
+        false,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            test_stub: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/transform-generated/input.js")]
+fn next_dynamic_transform_generated(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            transform_generated: Some(GeneratedOptionsHook::new(|options, call| {
+                options
+                    .props
+                    .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(Ident::new("analyticsId".into(), DUMMY_SP)),
+                        value: Box::new(call.module_id.into()),
+                    }))));
+            })),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/transition-names/input.js")]
+fn next_dynamic_transition_names(input: PathBuf) {
+    let mode = NextDynamicMode::Turbopack {
+        dynamic_transition_names: TransitionNames::new("next-dynamic")
+            .with_layer(TransitionLayer::EdgeServer, "next-dynamic-edge"),
+        key_export: Default::default(),
+    };
+    next_dynamic_config_run(
+        &input,
+        "output-server.js",
+        false,
+        true,
+        mode.clone(),
+        &Config::default,
+    );
+    next_dynamic_config_run(&input, "output-edge-server.js", false, true, mode, &|| {
+        Config {
+            runtime: DynamicRuntime::Edge,
+            ..Default::default()
+        }
+    });
+}
+
+// The generated locals are renamed when the module declares bindings with the
+// same names, while the imported exports keep theirs.
+#[fixture("tests/fixture/turbopack-hygiene/input.js")]
+fn next_dynamic_turbopack_hygiene(input: PathBuf) {
+    let mode = |dynamic_transition_name: &str, key_export: TurbopackKeyExport| {
+        NextDynamicMode::Turbopack {
+            dynamic_transition_names: dynamic_transition_name.into(),
+            key_export,
+        }
+    };
+    next_dynamic_config_run(
+        &input,
+        "output-turbo-dev-server.js",
+        true,
+        true,
+        mode("next-client-chunks", Default::default()),
+        &Config::default,
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-turbo-build-server.js",
+        false,
+        true,
+        mode("next-dynamic", Default::default()),
+        &Config::default,
+    );
+    next_dynamic_config_run(
+        &input,
+        "output-turbo-build-server-string-key.js",
+        false,
+        true,
+        mode("next-dynamic", TurbopackKeyExport::Named("some-key".into())),
+        &Config::default,
+    );
+}
+
+#[fixture("tests/fixture/vite/input.js")]
+fn next_dynamic_vite(input: PathBuf) {
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            next_dynamic(
+                false,
+                false,
+                false,
+                NextDynamicMode::Custom(Arc::new(ViteCodegen::new("tests/fixture/vite"))),
+                FileName::Real(PathBuf::from("tests/fixture/vite/src/pages/some-file.js")),
+                Some("tests/fixture/vite/src/pages".into()),
+                Default::default(),
+            )
+        },
+        &input,
+        &input.with_file_name("output.js"),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/webpack-resolver/input.js")]
+fn next_dynamic_webpack_resolver(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-prod.js",
+        false,
+        false,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            webpack_resolver: WebpackResolver::Resolve,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/wrapped-callee/input.js")]
+fn next_dynamic_wrapped_callee(input: PathBuf) {
+    next_dynamic_config_run(
+        &input,
+        "output-webpack-server.js",
+        false,
+        true,
+        NextDynamicMode::Webpack,
+        &|| Config {
+            transparent_wrappers: vec!["__wrapped".into()],
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,13 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            JSON.stringify({
+                id: "some-file.js -> " + "../components/hello",
+                chunks: [
+                    "static/chunks/components_hello.js"
+                ]
+            })
+        ]
+    }
+});
@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic'
+
+class Registry {
+  static Hello = dynamic(() => import('../components/hello'))
+
+  static {
+    this.Hero = dynamic(() => import('../components/hero'), { ssr: false })
+  }
+
+  @observable
+  static Chart = dynamic(() => import('../components/chart'))
+
+  @register(dynamic(() => import('../components/map')))
+  static Map
+}
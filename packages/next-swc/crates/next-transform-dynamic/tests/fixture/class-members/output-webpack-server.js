@@ -0,0 +1,36 @@
+import dynamic from 'next/dynamic';
+class Registry {
+    static Hello = dynamic(()=>import('../components/hello'), {
+        loadableGenerated: {
+            modules: [
+                "some-file.js -> " + "../components/hello"
+            ]
+        }
+    });
+    static{
+        this.Hero = dynamic(null, {
+            loadableGenerated: {
+                modules: [
+                    "some-file.js -> " + "../components/hero"
+                ]
+            },
+            ssr: false
+        });
+    }
+    @observable
+    static Chart = dynamic(()=>import('../components/chart'), {
+        loadableGenerated: {
+            modules: [
+                "some-file.js -> " + "../components/chart"
+            ]
+        }
+    });
+    @register(dynamic(()=>import('../components/map'), {
+        loadableGenerated: {
+            modules: [
+                "some-file.js -> " + "../components/map"
+            ]
+        }
+    }))
+    static Map;
+}
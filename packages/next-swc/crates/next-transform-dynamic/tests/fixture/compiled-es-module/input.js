@@ -0,0 +1,5 @@
+"use strict";
+Object.defineProperty(exports, "__esModule", { value: true });
+const dynamic_1 = require("next/dynamic");
+
+const DynamicComponent = (0, dynamic_1.default)(() => import('../components/hello'))
@@ -0,0 +1,17 @@
+import dynamic from 'next/dynamic'
+
+let DevTools = null
+if (process.env.NODE_ENV !== 'production') {
+  DevTools = dynamic(() => import('../components/dev-tools'))
+}
+
+const Chart =
+  typeof window === 'undefined'
+    ? null
+    : dynamic(() => import('../components/chart'))
+
+if (process.env.NODE_ENV === 'development') {
+  var DevPanel = dynamic(() => import('../components/dev-panel'))
+} else {
+  var ProdPanel = dynamic(() => import('../components/prod-panel'))
+}
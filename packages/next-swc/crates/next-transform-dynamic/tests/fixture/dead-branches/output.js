@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic';
+let DevTools = null;
+;
+const Chart = dynamic(()=>import('../components/chart'));
+{
+    var ProdPanel = dynamic(()=>import('../components/prod-panel'));
+    var DevPanel;
+}
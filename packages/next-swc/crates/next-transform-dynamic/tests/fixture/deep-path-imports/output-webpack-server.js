@@ -0,0 +1,18 @@
+import dynamic1 from 'next/dynamic.js';
+import dynamic2 from 'next/dist/shared/lib/dynamic';
+import dynamic3 from 'next/dist/client/dynamic';
+const DynamicComponent1 = dynamic1(()=>import('../components/hello1'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello1"
+        ]
+    }
+});
+const DynamicComponent2 = dynamic2(()=>import('../components/hello2'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello2"
+        ]
+    }
+});
+const DynamicComponent3 = dynamic3(()=>import('../components/hello3'));
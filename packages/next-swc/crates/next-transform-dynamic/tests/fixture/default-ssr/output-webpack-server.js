@@ -0,0 +1,17 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(null, {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+});
+const DynamicComponentWithSsr = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: true
+});
@@ -0,0 +1,34 @@
+import dynamic from 'next/dynamic'
+import loadable from 'next/dist/shared/lib/dynamic'
+
+const DynamicHero = dynamic(() => import('../components/hero'))
+
+export const DynamicChart = dynamic(() => import('../components/chart'), {
+  ssr: false,
+})
+
+const DynamicFooter = loadable(() => import('../components/footer'))
+
+let DynamicHeader
+DynamicHeader = dynamic(() => import('../components/header'))
+
+const components = {}
+components.Sidebar = dynamic(() => import('../components/sidebar'))
+
+export function getDynamicPage() {
+  const DynamicPage = dynamic(() => import('../components/page'))
+  return DynamicPage
+}
+
+// Generated in place of the `dynamic()` calls with `Config::test_stub`.
+const DynamicStub = (props) => createElement(require('../components/stub').default, props)
+
+const Title = function () {
+  return <h1>Title</h1>
+}
+
+const Named = function Named() {
+  return null
+}
+
+const render = () => null
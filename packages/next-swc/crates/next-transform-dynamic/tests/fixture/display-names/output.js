@@ -0,0 +1,32 @@
+import dynamic from 'next/dynamic';
+import loadable from 'next/dist/shared/lib/dynamic';
+const DynamicHero = dynamic(()=>import('../components/hero'));
+DynamicHero.displayName = "DynamicHero";
+export const DynamicChart = dynamic(()=>import('../components/chart'), {
+    ssr: false
+});
+DynamicChart.displayName = "DynamicChart";
+const DynamicFooter = loadable(()=>import('../components/footer'));
+DynamicFooter.displayName = "DynamicFooter";
+let DynamicHeader;
+DynamicHeader = dynamic(()=>import('../components/header'));
+DynamicHeader.displayName = "DynamicHeader";
+const components = {};
+components.Sidebar = dynamic(()=>import('../components/sidebar'));
+components.Sidebar.displayName = "Sidebar";
+export function getDynamicPage() {
+    const DynamicPage = dynamic(()=>import('../components/page'));
+    DynamicPage.displayName = "DynamicPage";
+    return DynamicPage;
+}
+// Generated in place of the `dynamic()` calls with `Config::test_stub`.
+const DynamicStub = (props)=>createElement(require('../components/stub').default, props);
+DynamicStub.displayName = "DynamicStub";
+const Title = function() {
+    return <h1>Title</h1>;
+};
+Title.displayName = "Title";
+const Named = function Named() {
+    return null;
+};
+const render = ()=>null;
@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+import * as eagerModule from "../components/hero";
+const DynamicHero = dynamic(()=>Promise.resolve(eagerModule), {
+    ssr: false
+});
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+});
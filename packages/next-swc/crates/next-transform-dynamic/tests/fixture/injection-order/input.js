@@ -0,0 +1,10 @@
+'use client'
+
+import dynamic from 'next/dynamic'
+import { Spinner } from '../components/spinner'
+
+const DynamicHero = dynamic(() => import('../components/hero'), { ssr: false })
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  loading: Spinner,
+})
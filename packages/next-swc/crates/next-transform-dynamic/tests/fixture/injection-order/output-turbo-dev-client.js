@@ -0,0 +1,23 @@
+'use client';
+import dynamic from 'next/dynamic';
+import { Spinner } from '../components/spinner';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hero";
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id1 } from "../components/hello";
+const DynamicHero = dynamic(()=>import('../components/hero'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    },
+    ssr: false
+});
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id1
+        ]
+    },
+    loading: Spinner
+});
@@ -0,0 +1,15 @@
+'use client';
+import dynamic from 'next/dynamic';
+import { Spinner } from '../components/spinner';
+import * as eagerModule from "../components/hero";
+const DynamicHero = dynamic(()=>Promise.resolve(eagerModule), {
+    ssr: false
+});
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    loading: Spinner
+});
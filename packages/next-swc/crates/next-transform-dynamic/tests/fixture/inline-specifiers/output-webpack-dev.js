@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+import * as eagerModule from "../components/hero";
+import { createElement as createElement } from "react";
+const DynamicHero = (props)=>createElement(eagerModule.default, props);
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+});
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic({
+  loader: () => import('../components/hello'),
+  loading: () => null,
+})
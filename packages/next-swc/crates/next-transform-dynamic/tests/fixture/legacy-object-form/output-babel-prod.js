@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic({
+    loader: ()=>import('../components/hello'),
+    loading: ()=>null,
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak('../components/hello')
+            ]
+    }
+});
@@ -0,0 +1,13 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+const DynamicComponent = dynamic({
+    loader: ()=>import('../components/hello'),
+    loading: ()=>null
+}, {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+});
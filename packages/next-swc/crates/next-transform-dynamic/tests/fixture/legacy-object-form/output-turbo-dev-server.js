@@ -0,0 +1,16 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { transition: next-client-chunks }";
+import id, { chunks as chunks } from "../components/hello";
+const DynamicComponent = dynamic({
+    loader: ()=>import('../components/hello'),
+    loading: ()=>null
+}, {
+    loadableGenerated: {
+        modules: [
+            JSON.stringify({
+                id: id,
+                chunks: chunks
+            })
+        ]
+    }
+});
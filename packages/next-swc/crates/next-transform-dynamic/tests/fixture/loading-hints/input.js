@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic'
+
+const DynamicModal = dynamic(() => import('../components/modal'), { prefetch: true, ssr: false })
@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+const DynamicModal = dynamic(()=>import('../components/modal'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/modal"
+        ]
+    },
+    prefetch: true,
+    ssr: false
+});
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+const DynamicChart = dynamic(() => import('../components/charts').then((mod) => mod.Chart))
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
+
+const DynamicModal = dynamic(() => import('../components/modal'), { prefetch: true, ssr: false })
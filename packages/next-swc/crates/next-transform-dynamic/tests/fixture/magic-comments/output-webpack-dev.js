@@ -0,0 +1,23 @@
+import dynamic from 'next/dynamic';
+const DynamicChart = dynamic(()=>import(/* webpackExports: ["Chart"] */ '../components/charts').then((mod)=>mod.Chart), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/charts"
+        ]
+    }
+});
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+const DynamicModal = dynamic(()=>import(/* webpackPrefetch: true */ '../components/modal'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/modal"
+        ]
+    },
+    ssr: false
+});
@@ -1,7 +1,7 @@
-"TURBOPACK { chunking-type: none }";
-import { __turbopack_module_id__ as id } from "../components/hello";
 import dynamic from 'next/dynamic';
 import somethingElse from 'something-else';
+"TURBOPACK { chunking-type: none }";
+import { __turbopack_module_id__ as id } from "../components/hello";
 const DynamicComponent = dynamic(()=>import('../components/hello'), {
     loadableGenerated: {
         modules: [
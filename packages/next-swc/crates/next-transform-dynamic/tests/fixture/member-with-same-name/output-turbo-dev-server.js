@@ -1,7 +1,7 @@
-"TURBOPACK { transition: next-client-chunks }";
-import id, { chunks as chunks } from "../components/hello";
 import dynamic from 'next/dynamic';
 import somethingElse from 'something-else';
+"TURBOPACK { transition: next-client-chunks }";
+import id, { chunks as chunks } from "../components/hello";
 const DynamicComponent = dynamic(()=>import('../components/hello'), {
     loadableGenerated: {
         modules: [
@@ -1,6 +1,6 @@
+import dynamic from 'next/dynamic';
 "TURBOPACK { chunking-type: none }";
 import { __turbopack_module_id__ as id } from "../components/hello";
-import dynamic from 'next/dynamic';
 const DynamicComponent = dynamic(()=>import('../components/hello'), {
     loadableGenerated: {
         modules: [
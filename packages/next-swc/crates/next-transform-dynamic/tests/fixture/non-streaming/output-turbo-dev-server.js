@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { transition: next-client-chunks }";
+import id from "../components/hello";
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+});
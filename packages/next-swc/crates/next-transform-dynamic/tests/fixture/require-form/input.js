@@ -0,0 +1,3 @@
+const dynamic = require('next/dynamic')
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,8 @@
+const dynamic = require('next/dynamic');
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak('../components/hello')
+            ]
+    }
+});
@@ -0,0 +1,10 @@
+"TURBOPACK { transition: next-dynamic }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+const dynamic = require('next/dynamic');
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+});
@@ -0,0 +1,13 @@
+"TURBOPACK { transition: next-client-chunks }";
+import id, { chunks as chunks } from "../components/hello";
+const dynamic = require('next/dynamic');
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            JSON.stringify({
+                id: id,
+                chunks: chunks
+            })
+        ]
+    }
+});
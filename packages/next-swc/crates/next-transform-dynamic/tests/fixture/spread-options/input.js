@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic'
+
+const common = { loading: () => null }
+
+const DynamicSpreadFirst = dynamic(() => import('../components/hello'), {
+  ...common,
+  ssr: false,
+})
+
+const DynamicSpreadLast = dynamic(() => import('../components/hello'), {
+  ssr: false,
+  ...common,
+})
+
+const DynamicLastLiteral = dynamic(() => import('../components/hello'), {
+  ssr: false,
+  ssr: true,
+})
@@ -0,0 +1,31 @@
+import dynamic from 'next/dynamic';
+const common = {
+    loading: ()=>null
+};
+const DynamicSpreadFirst = dynamic(null, {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ...common,
+    ssr: false
+});
+const DynamicSpreadLast = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false,
+    ...common
+});
+const DynamicLastLiteral = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false,
+    ssr: true
+});
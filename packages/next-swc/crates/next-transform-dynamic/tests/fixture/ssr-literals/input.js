@@ -0,0 +1,17 @@
+import dynamic from 'next/dynamic'
+
+const DynamicNegatedNumber = dynamic(() => import('../components/hello'), {
+  ssr: !1,
+})
+
+const DynamicDoubleNegation = dynamic(() => import('../components/hello'), {
+  ssr: !!false,
+})
+
+const DynamicBooleanCall = dynamic(() => import('../components/hello'), {
+  ssr: Boolean(false),
+})
+
+const DynamicServerRendered = dynamic(() => import('../components/hello'), {
+  ssr: !0,
+})
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+
+const DynamicStringKey = dynamic(() => import('../components/hello'), {
+  'ssr': false,
+})
+
+const DynamicComputedStringKey = dynamic(() => import('../components/hello'), {
+  ['ssr']: false,
+})
@@ -0,0 +1,17 @@
+import dynamic from 'next/dynamic';
+const DynamicStringKey = dynamic(null, {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    'ssr': false
+});
+const DynamicComputedStringKey = dynamic(null, {
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + "../components/hello"
+        ]
+    },
+    ['ssr']: false
+});
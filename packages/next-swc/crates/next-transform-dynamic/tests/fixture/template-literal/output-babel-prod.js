@@ -0,0 +1,23 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import(`../components/hello`), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak(`../components/hello`)
+            ]
+    }
+});
+const componentRoot = '@/some-components';
+const Component1 = dynamic(()=>import(`${componentRoot}/component1`), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak(`${componentRoot}/component1`)
+            ]
+    }
+});
+const Component2 = dynamic(()=>import(`${componentRoot}/component2`), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak(`${componentRoot}/component2`)
+            ]
+    }
+});
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const DynamicHero = dynamic(() => import('../components/hero'), { ssr: false })
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
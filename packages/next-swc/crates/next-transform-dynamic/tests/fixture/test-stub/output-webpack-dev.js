@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic';
+import { createElement as createElement } from "react";
+const DynamicHero = (props)=>createElement(require("../components/hero").default, props);
+const DynamicComponent = (props)=>createElement(require("../components/hello").default, props);
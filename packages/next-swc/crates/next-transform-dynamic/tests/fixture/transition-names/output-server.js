@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { transition: next-dynamic }";
+import { __turbopack_module_id__ as id } from "../components/hello";
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+});
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+const id = 'user-id'
+const chunks = ['user-chunk']
+const __turbopack_module_id__ = 'user-module-id'
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { transition: next-dynamic }";
+import { "some-key" as id } from "../components/hello";
+const id1 = 'user-id';
+const chunks = [
+    'user-chunk'
+];
+const __turbopack_module_id__ = 'user-module-id';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id
+        ]
+    }
+});
@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic';
+"TURBOPACK { transition: next-client-chunks }";
+import id, { chunks as chunks } from "../components/hello";
+const id1 = 'user-id';
+const chunks1 = [
+    'user-chunk'
+];
+const __turbopack_module_id__ = 'user-module-id';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            JSON.stringify({
+                id: id,
+                chunks: chunks
+            })
+        ]
+    }
+});
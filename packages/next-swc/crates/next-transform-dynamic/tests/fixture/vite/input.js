@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+
+const DynamicHello = dynamic(() => import('../components/hello'))
+
+const DynamicChart = dynamic(() => import('../components/chart'))
+
+const DynamicMissing = dynamic(() => import('../components/missing'))
+
+const DynamicLibrary = dynamic(() => import('chart-library'), { ssr: false })
@@ -0,0 +1,33 @@
+import dynamic from 'next/dynamic';
+const DynamicHello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/components/hello.tsx"
+        ],
+        vite: ()=>Object.keys(import.meta.glob("../components/hello.tsx"))
+    }
+});
+const DynamicChart = dynamic(()=>import('../components/chart'), {
+    loadableGenerated: {
+        modules: [
+            "src/components/chart/index.jsx"
+        ],
+        vite: ()=>Object.keys(import.meta.glob("../components/chart/index.jsx"))
+    }
+});
+const DynamicMissing = dynamic(()=>import('../components/missing'), {
+    loadableGenerated: {
+        modules: [
+            "src/components/missing"
+        ],
+        vite: ()=>Object.keys(import.meta.glob("../components/missing.{mjs,js,mts,ts,jsx,tsx,json}"))
+    }
+});
+const DynamicLibrary = dynamic(()=>import('chart-library'), {
+    loadableGenerated: {
+        modules: [
+            "chart-library"
+        ]
+    },
+    ssr: false
+});
@@ -0,0 +1,26 @@
+import dynamic from 'next/dynamic';
+const DynamicComponentWithCustomLoading = dynamic(()=>import('../components/hello'), {
+    loading: ()=><p >...</p>,
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + '../components/hello'
+        ]
+    }
+});
+const DynamicClientOnlyComponent = dynamic(()=>import('../components/hello'), {
+    ssr: false,
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + '../components/hello'
+        ]
+    }
+});
+const DynamicClientOnlyComponentWithSuspense = dynamic(()=>import('../components/hello'), {
+    ssr: false,
+    suspense: true,
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + '../components/hello'
+        ]
+    }
+});
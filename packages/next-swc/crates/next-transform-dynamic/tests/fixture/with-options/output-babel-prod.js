@@ -0,0 +1,26 @@
+import dynamic from 'next/dynamic';
+const DynamicComponentWithCustomLoading = dynamic(()=>import('../components/hello'), {
+    loading: ()=><p >...</p>,
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak('../components/hello')
+            ]
+    }
+});
+const DynamicClientOnlyComponent = dynamic(()=>import('../components/hello'), {
+    ssr: false,
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak('../components/hello')
+            ]
+    }
+});
+const DynamicClientOnlyComponentWithSuspense = dynamic(()=>import('../components/hello'), {
+    ssr: false,
+    suspense: true,
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak('../components/hello')
+            ]
+    }
+});
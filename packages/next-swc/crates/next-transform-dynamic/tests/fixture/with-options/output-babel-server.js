@@ -0,0 +1,16 @@
+import dynamic from 'next/dynamic';
+const DynamicComponentWithCustomLoading = dynamic(()=>import('../components/hello'), {
+    loading: ()=><p >...</p>,
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + '../components/hello'
+        ]
+    }
+});
+const DynamicClientOnlyComponent = dynamic(async ()=>null, {
+    ssr: false
+});
+const DynamicClientOnlyComponentWithSuspense = dynamic(async ()=>null, {
+    ssr: false,
+    suspense: true
+});
@@ -1,10 +1,6 @@
+import dynamic from 'next/dynamic';
 "TURBOPACK { transition: next-dynamic }";
 import { __turbopack_module_id__ as id } from "../components/hello";
-"TURBOPACK { transition: next-dynamic }";
-import { __turbopack_module_id__ as id1 } from "../components/hello";
-"TURBOPACK { transition: next-dynamic }";
-import { __turbopack_module_id__ as id2 } from "../components/hello";
-import dynamic from 'next/dynamic';
 const DynamicComponentWithCustomLoading = dynamic(()=>import('../components/hello'), {
     loadableGenerated: {
         modules: [
@@ -16,7 +12,7 @@ const DynamicComponentWithCustomLoading = dynamic(()=>import('../components/hell
 const DynamicClientOnlyComponent = dynamic(null, {
     loadableGenerated: {
         modules: [
-            id1
+            id
         ]
     },
     ssr: false
@@ -24,7 +20,7 @@ const DynamicClientOnlyComponent = dynamic(null, {
 const DynamicClientOnlyComponentWithSuspense = dynamic(()=>import('../components/hello'), {
     loadableGenerated: {
         modules: [
-            id2
+            id
         ]
     },
     ssr: false,
@@ -1,10 +1,6 @@
+import dynamic from 'next/dynamic';
 "TURBOPACK { transition: next-client-chunks }";
 import id, { chunks as chunks } from "../components/hello";
-"TURBOPACK { transition: next-client-chunks }";
-import id1, { chunks as chunks1 } from "../components/hello";
-"TURBOPACK { transition: next-client-chunks }";
-import id2, { chunks as chunks2 } from "../components/hello";
-import dynamic from 'next/dynamic';
 const DynamicComponentWithCustomLoading = dynamic(()=>import('../components/hello'), {
     loadableGenerated: {
         modules: [
@@ -20,8 +16,8 @@ const DynamicClientOnlyComponent = dynamic(null, {
     loadableGenerated: {
         modules: [
             JSON.stringify({
-                id: id1,
-                chunks: chunks1
+                id: id,
+                chunks: chunks
             })
         ]
     },
@@ -31,8 +27,8 @@ const DynamicClientOnlyComponentWithSuspense = dynamic(()=>import('../components
     loadableGenerated: {
         modules: [
             JSON.stringify({
-                id: id2,
-                chunks: chunks2
+                id: id,
+                chunks: chunks
             })
         ]
     },
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+var _dynamic = _interopRequireDefault(require('next/dynamic'))
+
+const Interop = (0, _dynamic.default)(() => import('../components/interop'))
+const Sequence = (0, dynamic)(() => import('../components/sequence'))
+const Wrapped = __wrapped(dynamic)(() => import('../components/wrapped'))
+const Unknown = __unknown(dynamic)(() => import('../components/unknown'))
@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>handleImport(import('./components/hello')), {
+    loading: ()=>null,
+    ssr: false,
+    loadableGenerated: {
+        modules: [
+            "some-file.js -> " + './components/hello'
+        ]
+    }
+});
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(async ()=>null, {
+    loading: ()=>null,
+    ssr: false
+});
@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic'
+import type { Props } from '../components/hello'
+
+const DynamicComponent = dynamic<Props>(
+  (): Promise<React.ComponentType<Props>> => import('../components/hello'),
+  { ssr: false }
+)
+
+const DynamicHero = dynamic(() => import('../components/hero'), {
+  loading: (): JSX.Element => null as unknown as JSX.Element,
+})
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic';
+import type { Props } from '../components/hello';
+import { createElement as createElement } from "react";
+const DynamicComponent = (props: Props)=>createElement(require("../components/hello").default, props);
+const DynamicHero = (props: any)=>createElement(require("../components/hero").default, props);
@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic';
+import type { Props } from '../components/hello';
+const DynamicComponent = dynamic<Props>(null!, {
+    loadableGenerated: {
+        modules: [
+            "some-file.ts -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+});
+const DynamicHero = dynamic(()=>import('../components/hero'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.ts -> " + "../components/hero"
+        ]
+    },
+    loading: (): JSX.Element=>null as unknown as JSX.Element
+});
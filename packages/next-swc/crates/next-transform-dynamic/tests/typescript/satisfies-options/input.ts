@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic'
+import type { DynamicOptions } from 'next/dynamic'
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  ssr: false,
+} satisfies DynamicOptions)
+
+const DynamicHero = dynamic(() => import('../components/hero'), {
+  loading: () => null,
+} as const)
@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic';
+import type { DynamicOptions } from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.ts -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+} satisfies DynamicOptions);
+const DynamicHero = dynamic(()=>import('../components/hero'), {
+    loadableGenerated: {
+        modules: [
+            "some-file.ts -> " + "../components/hero"
+        ]
+    },
+    loading: ()=>null
+} as const);
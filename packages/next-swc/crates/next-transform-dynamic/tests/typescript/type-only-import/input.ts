@@ -0,0 +1,3 @@
+import type dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -301,6 +301,7 @@ export function getLoaderSWCOptions({
   filename,
   development,
   isServer,
+  isEdgeServer,
   pagesDir,
   appDir,
   isPageFile,
@@ -322,6 +323,7 @@ export function getLoaderSWCOptions({
   filename: string
   development: boolean
   isServer: boolean
+  isEdgeServer?: boolean
   pagesDir?: string
   appDir?: string
   isPageFile: boolean
@@ -392,6 +394,14 @@ export function getLoaderSWCOptions({
     }
   }
 
+  // Removes the branches ruled out by the `DefinePlugin` values before the
+  // `next/dynamic` transform and the edge runtime check.
+  baseOptions.deadBranches = {
+    NODE_ENV: development ? 'development' : 'production',
+    NEXT_RUNTIME: isEdgeServer ? 'edge' : isServer ? 'nodejs' : '',
+    'typeof window': isServer ? 'undefined' : 'object',
+  }
+
   const isNextDist = nextDistPath.test(filename)
 
   if (isServer) {
@@ -402,6 +412,7 @@ export function getLoaderSWCOptions({
       disablePageConfig: true,
       isDevelopment: development,
       isServerCompiler: isServer,
+      isEdgeRuntime: !!isEdgeServer,
       pagesDir,
       appDir,
       isPageFile,
@@ -417,6 +417,7 @@ export default async function getBaseWebpackConfig(
       loader: 'next-swc-loader',
       options: {
         isServer: isNodeOrEdgeCompilation,
+        isEdgeServer,
         rootDir: dir,
         pagesDir,
         appDir,
@@ -34,6 +34,7 @@ import path, { isAbsolute } from 'path'
 export interface SWCLoaderOptions {
   rootDir: string
   isServer: boolean
+  isEdgeServer?: boolean
   pagesDir?: string
   appDir?: string
   hasReactRefresh: boolean
@@ -59,6 +60,7 @@ async function loaderTransform(
 
   const {
     isServer,
+    isEdgeServer,
     rootDir,
     pagesDir,
     appDir,
@@ -78,6 +80,7 @@ async function loaderTransform(
     appDir,
     filename,
     isServer,
+    isEdgeServer,
     isPageFile,
     development: this.mode === 'development',
     hasReactRefresh,
@@ -18,6 +18,8 @@ export type LoaderMap = { [module: string]: () => Loader<any> }
 export type LoadableGeneratedOptions = {
   webpack?(): any
   modules?(): LoaderMap
+  // Set by the SWC transform in development when it can't track the loader
+  error?: string
 }
 
 export type DynamicOptionsLoadingProps = {
@@ -134,6 +136,15 @@ export default function dynamic<P = {}>(
     delete loadableOptions.loadableGenerated
   }
 
+  // Report the compile-time warning where the component is created, as its
+  // module can't be preloaded
+  if (loadableOptions.error) {
+    if (process.env.NODE_ENV !== 'production') {
+      console.error(loadableOptions.error)
+    }
+    delete loadableOptions.error
+  }
+
   // support for disabling server side rendering, eg: dynamic(() => import('../hello-world'), {ssr: false}).
   if (typeof loadableOptions.ssr === 'boolean' && !loadableOptions.ssr) {
     delete loadableOptions.webpack